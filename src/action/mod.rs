@@ -0,0 +1,31 @@
+// What the player asked for, whichever key or click it came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Type(char),
+    Delete,
+    Clear,
+    Submit,
+    Quit,
+    Restart,
+    Hint,
+    ShowStats,
+    Help,
+    Settings,
+    Theme,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    // A clicked menu or settings entry.
+    Choose(usize)
+}
+
+// How the game took an action, for the front end to react to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Response {
+    Continue,
+    Rejected,
+    Quit
+}
@@ -0,0 +1,159 @@
+use ratatui::layout::Rect;
+
+// Frames are advanced on every 50 ms event poll timeout.
+const FLIP_FRAMES: u16 = 4;
+const FLIP_STAGGER: u16 = 2;
+const FLIP_SCALES: [u16; FLIP_FRAMES as usize] = [60, 20, 20, 60];
+const SHAKE_OFFSETS: [i16; 8] = [-2, 2, -2, 2, -1, 1, -1, 1];
+const BOUNCE_FRAMES: u16 = 3;
+const BOUNCE_STAGGER: u16 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnimationKind {
+    Flip,
+    Shake,
+    Bounce
+}
+
+// How a single tile is drawn in the current frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TileEffect {
+    pub hidden: bool,
+    pub scale: u16,
+    pub dx: i16,
+    pub dy: i16
+}
+
+impl Default for TileEffect {
+    fn default() -> Self {
+        Self { hidden: false, scale: 100, dx: 0, dy: 0 }
+    }
+}
+
+impl TileEffect {
+
+    // Moves and squashes the tile area while keeping it inside `bounds`.
+    pub fn apply(&self, area: Rect, bounds: Rect) -> Rect {
+        let height = (area.height * self.scale / 100).max(1);
+        let y = area.y as i32 + (area.height - height) as i32 / 2 + self.dy as i32;
+        let x = area.x as i32 + self.dx as i32;
+
+        let x = x.clamp(bounds.x as i32, (bounds.right() as i32 - area.width as i32).max(bounds.x as i32)) as u16;
+        let y = y.clamp(bounds.y as i32, (bounds.bottom() as i32 - height as i32).max(bounds.y as i32)) as u16;
+        Rect::new(x, y, area.width, height).intersection(bounds)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Animation {
+    kind: AnimationKind,
+    row: usize,
+    word_len: usize,
+    frame: u16,
+    then: Option<AnimationKind>
+}
+
+impl Animation {
+
+    pub fn new(kind: AnimationKind, row: usize, word_len: usize) -> Self {
+        Self { kind, row, word_len, frame: 0, then: None }
+    }
+
+    pub fn then(mut self, kind: AnimationKind) -> Self {
+        self.then = Some(kind);
+        self
+    }
+
+    #[cfg(test)]
+    pub fn kind(&self) -> AnimationKind {
+        self.kind
+    }
+
+    fn duration(&self) -> u16 {
+        let last_tile = self.word_len.saturating_sub(1) as u16;
+        match self.kind {
+            AnimationKind::Flip => last_tile * FLIP_STAGGER + FLIP_FRAMES,
+            AnimationKind::Shake => SHAKE_OFFSETS.len() as u16,
+            AnimationKind::Bounce => last_tile * BOUNCE_STAGGER + BOUNCE_FRAMES
+        }
+    }
+
+    // Advances one frame and returns whether the animation is over.
+    pub fn tick(&mut self) -> bool {
+        self.frame += 1;
+        if self.frame < self.duration() {
+            return false;
+        }
+
+        match self.then.take() {
+            Some(kind) => {
+                self.kind = kind;
+                self.frame = 0;
+                false
+            },
+            None => true
+        }
+    }
+
+    pub fn tile(&self, row: usize, index: usize) -> TileEffect {
+        if row != self.row {
+            return TileEffect::default();
+        }
+
+        let index = index as u16;
+        match self.kind {
+            AnimationKind::Flip => match self.frame.checked_sub(index * FLIP_STAGGER) {
+                None => TileEffect { hidden: true, ..TileEffect::default() },
+                Some(local) if local < FLIP_FRAMES => TileEffect {
+                    hidden: local < FLIP_FRAMES / 2,
+                    scale: FLIP_SCALES[local as usize],
+                    ..TileEffect::default()
+                },
+                Some(_) => TileEffect::default()
+            },
+            AnimationKind::Shake => TileEffect { dx: SHAKE_OFFSETS[self.frame as usize], ..TileEffect::default() },
+            AnimationKind::Bounce => match self.frame.checked_sub(index * BOUNCE_STAGGER) {
+                Some(local) if local < BOUNCE_FRAMES - 1 => TileEffect { dy: -1, ..TileEffect::default() },
+                _ => TileEffect::default()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+    use crate::animation::{Animation, AnimationKind, TileEffect};
+
+    #[test]
+    fn should_reveal_tiles_left_to_right() {
+        let mut animation = Animation::new(AnimationKind::Flip, 2, 5);
+        animation.tick();
+        animation.tick();
+        animation.tick();
+
+        assert!(!animation.tile(2, 0).hidden);
+        assert!(animation.tile(2, 1).hidden);
+        assert!(animation.tile(2, 4).hidden);
+        assert_eq!(animation.tile(1, 4), TileEffect::default());
+    }
+
+    #[test]
+    fn should_bounce_after_flipping() {
+        let mut animation = Animation::new(AnimationKind::Flip, 0, 5).then(AnimationKind::Bounce);
+        while animation.kind() == AnimationKind::Flip {
+            assert!(!animation.tick());
+        }
+        assert_eq!(animation.tile(0, 0).dy, -1);
+        while !animation.tick() {}
+        assert_eq!(animation.tile(0, 4), TileEffect::default());
+    }
+
+    #[test]
+    fn should_keep_shaken_tiles_inside_bounds() {
+        let effect = TileEffect { dx: -2, ..TileEffect::default() };
+        let bounds = Rect::new(0, 0, 20, 10);
+        assert_eq!(effect.apply(Rect::new(1, 2, 5, 3), bounds), Rect::new(0, 2, 5, 3));
+        assert_eq!(effect.apply(Rect::new(10, 2, 5, 3), bounds), Rect::new(8, 2, 5, 3));
+    }
+}
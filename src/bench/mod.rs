@@ -0,0 +1,526 @@
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use serde::Serialize;
+use crate::config::Config;
+use crate::decision_tree::DecisionTree;
+use crate::game_manager::{GameManager, GameManagerState};
+use crate::game_state::{GameState, GameStatus, GameUpdateResult};
+use crate::history::History;
+use crate::language::Language;
+use crate::letters::LetterState;
+use crate::protocol;
+use crate::settings::{DEFAULT_TRIES, DEFAULT_WORD_LENGTH};
+use crate::solver::Solver;
+use crate::stats::Stats;
+use crate::words::WordPack;
+
+const USAGE: &str = "Usage: wordle bench [options]
+
+Plays every answer of a word list with a solver and reports how many guesses it needed.
+
+Options:
+  --solver <command>        Run a program speaking `--protocol jsonl` instead of the built-in solver,
+                            one copy per thread, run by sh, e.g. --solver \"python3 bot.py\"
+  --tree                    Follow the built-in solver's decision tree, built once and then cached
+  --words <pack>            Answers to play, by default the pack of the language
+  --lang en|pl              Language of the words
+  --length <n>              Word length
+  --tries <n>               Number of tries
+  --hard                    Hard mode, every revealed hint has to be used
+  --threads <n>             Games played at once, by default one per core
+  --output <file>           Also write every game to a .csv or .json file, can be repeated";
+
+// Games listed as the worst cases.
+const WORST: usize = 10;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BenchOptions {
+    pub solver: Option<String>,
+    pub words: Option<String>,
+    pub language: Option<String>,
+    pub length: Option<usize>,
+    pub tries: Option<u8>,
+    pub hard: bool,
+    pub threads: Option<usize>,
+    pub outputs: Vec<String>,
+    pub tree: bool,
+    pub help: bool
+}
+
+impl BenchOptions {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = BenchOptions::default();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().cloned().ok_or_else(|| format!("Missing value for {}", arg));
+            match arg.as_str() {
+                "--solver" => options.solver = Some(value()?),
+                "--words" => options.words = Some(value()?),
+                "--lang" => options.language = Some(value()?),
+                "--length" => options.length = Some(number(&value()?, arg)?),
+                "--tries" => options.tries = Some(number(&value()?, arg)?),
+                "--hard" => options.hard = true,
+                "--tree" => options.tree = true,
+                "--threads" => options.threads = Some(number(&value()?, arg)?).filter(|threads| *threads > 0),
+                "--output" | "-o" => options.outputs.push(value()?),
+                "--help" | "-h" => options.help = true,
+                other => return Err(format!("Unknown option '{}'\n\n{}", other, USAGE))
+            }
+        }
+
+        for output in &options.outputs {
+            Format::of(output)?;
+        }
+        Ok(options)
+    }
+}
+
+fn number<T: std::str::FromStr>(value: &str, flag: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("Invalid value '{}' for {}", value, flag))
+}
+
+enum Format {
+    Csv,
+    Json
+}
+
+impl Format {
+    fn of(path: &str) -> Result<Self, String> {
+        match Path::new(path).extension().and_then(|extension| extension.to_str()) {
+            Some("csv") => Ok(Format::Csv),
+            Some("json") => Ok(Format::Json),
+            _ => Err(format!("Can't tell the format of '{}', expected a .csv or .json file", path))
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Game {
+    pub answer: String,
+    pub won: bool,
+    pub guesses: Vec<String>
+}
+
+impl Game {
+    fn from_state(state: &GameState) -> Self {
+        Self {
+            answer: state.target_word.clone(),
+            won: state.status == GameStatus::Won,
+            guesses: state.guessed_words()
+        }
+    }
+}
+
+// What a run is compared by, written next to the games in JSON exports.
+#[derive(Debug, Serialize)]
+struct Report<'a> {
+    solver: &'a str,
+    words: &'a str,
+    length: usize,
+    tries: u8,
+    hard: bool,
+    played: usize,
+    won: usize,
+    average: f64,
+    distribution: Vec<usize>,
+    failures: Vec<&'a str>,
+    games: &'a [Game]
+}
+
+impl<'a> Report<'a> {
+    fn new(solver: &'a str, words: &'a str, length: usize, tries: u8, hard: bool, games: &'a [Game]) -> Self {
+        let won: Vec<&Game> = games.iter().filter(|game| game.won).collect();
+        let mut distribution = vec![0; tries as usize];
+        for game in &won {
+            distribution[game.guesses.len() - 1] += 1;
+        }
+        let average = match won.len() {
+            0 => 0.0,
+            count => won.iter().map(|game| game.guesses.len()).sum::<usize>() as f64 / count as f64
+        };
+
+        Self {
+            solver,
+            words,
+            length,
+            tries,
+            hard,
+            played: games.len(),
+            won: won.len(),
+            average,
+            distribution,
+            failures: games.iter().filter(|game| !game.won).map(|game| game.answer.as_str()).collect(),
+            games
+        }
+    }
+
+    // Lost games first, then the ones taking the most guesses.
+    fn worst(&self) -> Vec<&Game> {
+        let mut games: Vec<&Game> = self.games.iter().collect();
+        games.sort_by_key(|game| (game.won, std::cmp::Reverse(game.guesses.len())));
+        games.truncate(WORST);
+        games
+    }
+
+    fn print(&self) {
+        let mode = if self.hard { "hard" } else { "normal" };
+        println!("{} on '{}', {} letters, {} tries, {} mode", self.solver, self.words, self.length, self.tries, mode);
+        println!("Played {}, won {} ({:.1}%), {:.3} guesses on average",
+            self.played, self.won, self.won as f64 * 100.0 / self.played.max(1) as f64, self.average);
+
+        let most = self.distribution.iter().copied().max().unwrap_or(0).max(1);
+        println!("\nGuess distribution:");
+        for (i, count) in self.distribution.iter().enumerate() {
+            println!("{:>3} {} {}", i + 1, "█".repeat((count * 30).div_ceil(most)), count);
+        }
+
+        if !self.failures.is_empty() {
+            println!("\nFailed: {}", self.failures.join(", "));
+        }
+        println!("\nWorst cases:");
+        for game in self.worst() {
+            let result = if game.won { game.guesses.len().to_string() } else { "X".to_string() };
+            println!("{:>3} {:<10} {}", result, game.answer, game.guesses.join(" "));
+        }
+    }
+
+    fn csv(&self) -> String {
+        let mut csv = "answer,won,guesses,words\n".to_string();
+        for game in self.games {
+            csv += &format!("{},{},{},{}\n", game.answer, game.won, game.guesses.len(), game.guesses.join(" "));
+        }
+        csv
+    }
+
+    fn export(&self, path: &str) -> Result<(), String> {
+        let contents = match Format::of(path)? {
+            Format::Csv => self.csv(),
+            Format::Json => serde_json::to_string_pretty(self).map_err(|error| error.to_string())? + "\n"
+        };
+        fs::write(path, contents).map_err(|error| format!("Couldn't write '{}': {}", path, error))
+    }
+}
+
+// The words to play, with the alphabet they're typed in.
+struct Setup {
+    pack: WordPack,
+    language: Language,
+    tries: u8,
+    hard: bool
+}
+
+impl Setup {
+    fn new(options: &BenchOptions) -> Result<Self, String> {
+        let language = options.language.as_deref().map(Language::parse).transpose()?;
+        let pack = match &options.words {
+            Some(reference) => WordPack::resolve(reference)?,
+            None => {
+                let code = language.as_ref().map(|language| language.pack.clone()).unwrap_or(Language::english().pack);
+                WordPack::embedded(&code).ok_or(format!("Missing embedded pack '{}'", code))?
+            }
+        };
+        let length = options.length.unwrap_or(DEFAULT_WORD_LENGTH as usize);
+        let pack = pack.with_length(length);
+        if pack.len() == 0 {
+            return Err(format!("'{}' has no {}-letter words", pack.name(), length));
+        }
+
+        let language = match language {
+            Some(language) => language.with_letters_of(&pack),
+            None => Language::for_pack(&pack)
+        };
+        Ok(Self { pack, language, tries: options.tries.unwrap_or(DEFAULT_TRIES), hard: options.hard })
+    }
+
+    fn game(&self, answer: &str) -> GameState {
+        let mut state = GameState::new(answer, self.tries, &self.language.alphabet);
+        state.set_hard_mode(self.hard);
+        state.set_dictionary(self.pack.words());
+        state
+    }
+}
+
+// A solver playing one game at a time.
+trait Player {
+    fn play(&mut self, state: GameState) -> Result<Game, String>;
+}
+
+struct BuiltIn<'a> {
+    words: &'a [String],
+    // The first guess is the same for every answer, so it's only worked out once.
+    opening: Option<String>
+}
+
+impl Player for BuiltIn<'_> {
+    fn play(&mut self, mut state: GameState) -> Result<Game, String> {
+        let mut solver = Solver::new(self.words);
+
+        while state.status == GameStatus::Pending {
+            let guess = match (state.guess_count(), &self.opening) {
+                (0, Some(opening)) => opening.clone(),
+                _ => solver.suggest().ok_or(format!("No word left for '{}'", state.target_word))?.to_string()
+            };
+            let feedback = enter(&mut state, &guess)?;
+            solver.apply(&guess, &feedback);
+        }
+
+        Ok(Game::from_state(&state))
+    }
+}
+
+struct Tree<'a> {
+    tree: &'a DecisionTree
+}
+
+impl Player for Tree<'_> {
+    fn play(&mut self, mut state: GameState) -> Result<Game, String> {
+        let mut node = self.tree;
+
+        while state.status == GameStatus::Pending {
+            let feedback = enter(&mut state, node.guess())?;
+            if state.status != GameStatus::Pending {
+                break;
+            }
+            node = node.after(&feedback).ok_or(format!("The decision tree has no guess left for '{}'", state.target_word))?;
+        }
+
+        Ok(Game::from_state(&state))
+    }
+}
+
+// Types and submits a guess, returning its colours.
+fn enter(state: &mut GameState, guess: &str) -> Result<Vec<LetterState>, String> {
+    for letter in guess.chars() {
+        state.push_letter(letter);
+    }
+    if let GameUpdateResult::Rejected(rejection) = state.commit_guess() {
+        return Err(format!("'{}' was rejected while guessing '{}': {:?}", guess, state.target_word, rejection));
+    }
+    Ok(state.get(state.guess_count() - 1).letters().iter().map(|letter| letter.state.clone()).collect())
+}
+
+// A program playing through the bot protocol, with the games refereed by a game manager.
+struct External {
+    child: Child,
+    // Taken when dropped, as closing it tells the solver there are no more games.
+    input: Option<ChildStdin>,
+    output: BufReader<ChildStdout>,
+    manager: GameManager
+}
+
+impl External {
+    fn spawn(command: &str, language: &Language) -> Result<Self, String> {
+        if command.trim().is_empty() {
+            return Err("Missing solver command".to_string());
+        }
+        // Through the shell, so quoted arguments and paths with spaces work as typed.
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|error| format!("Couldn't run '{}': {}", command, error))?;
+
+        let input = child.stdin.take().ok_or("Couldn't write to the solver")?;
+        let output = BufReader::new(child.stdout.take().ok_or("Couldn't read from the solver")?);
+        let mut manager = GameManager::new(History::in_memory(), Stats::in_memory());
+        manager.configure(&Config { language: Some(language.code.clone()), ..Config::default() });
+        Ok(Self { child, input: Some(input), output, manager })
+    }
+}
+
+impl Player for External {
+    fn play(&mut self, state: GameState) -> Result<Game, String> {
+        let input = self.input.as_mut().ok_or("The solver was stopped")?;
+        play_through_protocol(&mut self.manager, state, &mut self.output, input)
+    }
+}
+
+impl Drop for External {
+    fn drop(&mut self) {
+        drop(self.input.take());
+        let _ = self.child.wait();
+    }
+}
+
+fn play_through_protocol(manager: &mut GameManager, state: GameState, input: &mut impl BufRead, output: &mut impl Write) -> Result<Game, String> {
+    manager.state = GameManagerState::Playing(Box::new(state));
+    let finished = protocol::referee(manager, input, output).map_err(|error| format!("Lost the solver: {}", error))?;
+
+    match &manager.state {
+        GameManagerState::Playing(state) if finished => Ok(Game::from_state(state)),
+        GameManagerState::Playing(state) => Err(format!("The solver stopped while guessing '{}'", state.target_word)),
+        _ => Err("The game was lost track of".to_string())
+    }
+}
+
+// Plays every answer, spreading them over threads which each take the next
+// answer not yet played.
+fn play_all<P: Player>(answers: &[String], threads: usize, player: impl Fn() -> Result<P, String> + Sync, setup: &Setup) -> Result<Vec<Game>, String> {
+    let next = AtomicUsize::new(0);
+
+    let results: Vec<Result<Vec<(usize, Game)>, String>> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.min(answers.len())).map(|_| scope.spawn(|| {
+            let mut player = player()?;
+            let mut games = Vec::new();
+            loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(answer) = answers.get(i) else {
+                    return Ok(games);
+                };
+                games.push((i, player.play(setup.game(answer))?));
+            }
+        })).collect();
+
+        workers.into_iter().map(|worker| worker.join().unwrap_or_else(|_| Err("A solver thread panicked".to_string()))).collect()
+    });
+
+    let mut games = Vec::with_capacity(answers.len());
+    for result in results {
+        games.extend(result?);
+    }
+    games.sort_by_key(|(i, _)| *i);
+    Ok(games.into_iter().map(|(_, game)| game).collect())
+}
+
+pub fn run(options: &BenchOptions) -> Result<(), String> {
+    if options.help {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+
+    let setup = Setup::new(options)?;
+    let words = setup.pack.words();
+    let threads = options.threads
+        .or(thread::available_parallelism().ok().map(usize::from))
+        .unwrap_or(1);
+
+    let (name, games) = match &options.solver {
+        Some(_) if options.tree => return Err("--tree is for the built-in solver, it can't be used with --solver".to_string()),
+        Some(command) => {
+            let games = play_all(words, threads, || External::spawn(command, &setup.language), &setup)?;
+            (command.as_str(), games)
+        },
+        None if options.tree => {
+            let tree = DecisionTree::load_or_build(words, setup.tries, setup.hard)?;
+            let games = play_all(words, threads, || Ok(Tree { tree: &tree }), &setup)?;
+            ("Decision tree", games)
+        },
+        None => {
+            let opening = Solver::new(words).suggest().map(str::to_string);
+            let games = play_all(words, threads, || Ok(BuiltIn { words, opening: opening.clone() }), &setup)?;
+            ("Built-in solver", games)
+        }
+    };
+
+    let length = options.length.unwrap_or(DEFAULT_WORD_LENGTH as usize);
+    let report = Report::new(name, setup.pack.name(), length, setup.tries, setup.hard, &games);
+    report.print();
+    for output in &options.outputs {
+        report.export(output)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bench::{play_all, play_through_protocol, BenchOptions, BuiltIn, External, Game, Player, Report, Setup, Tree};
+    use crate::decision_tree::DecisionTree;
+    use crate::config::Config;
+    use crate::game_manager::GameManager;
+    use crate::history::History;
+    use crate::stats::Stats;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    fn game(answer: &str, won: bool, guesses: &[&str]) -> Game {
+        Game { answer: answer.to_string(), won, guesses: guesses.iter().map(|guess| guess.to_string()).collect() }
+    }
+
+    #[test]
+    fn should_parse_options() {
+        let options = BenchOptions::parse(&args("--words en --length 4 --hard --threads 2 -o out.csv --output out.json")).unwrap();
+
+        assert_eq!(options.words.as_deref(), Some("en"));
+        assert_eq!(options.length, Some(4));
+        assert!(options.hard);
+        assert_eq!(options.threads, Some(2));
+        assert_eq!(options.outputs, args("out.csv out.json"));
+        assert!(BenchOptions::parse(&args("--output out.txt")).is_err());
+        assert!(BenchOptions::parse(&args("--tries")).is_err());
+    }
+
+    #[test]
+    fn should_solve_every_answer_with_the_built_in_solver_across_threads() {
+        let setup = Setup::new(&BenchOptions { words: Some("en".to_string()), length: Some(4), ..BenchOptions::default() }).unwrap();
+        let words = setup.pack.words();
+        let player = || Ok(BuiltIn { words, opening: None });
+
+        let games = play_all(words, 4, player, &setup).unwrap();
+
+        assert_eq!(games.iter().map(|game| game.answer.clone()).collect::<Vec<_>>(), words);
+        assert!(games.iter().all(|game| game.won && game.guesses.last() == Some(&game.answer)));
+        assert_eq!(games, play_all(words, 1, player, &setup).unwrap());
+    }
+
+    #[test]
+    fn should_play_by_the_decision_tree() {
+        let setup = Setup::new(&BenchOptions { words: Some("en".to_string()), length: Some(6), tries: Some(4), ..BenchOptions::default() }).unwrap();
+        let words = setup.pack.words();
+        let tree = DecisionTree::build(words, 4, false).unwrap();
+
+        let games = play_all(words, 2, || Ok(Tree { tree: &tree }), &setup).unwrap();
+        let built_in = play_all(words, 2, || Ok(BuiltIn { words, opening: None }), &setup).unwrap();
+
+        assert!(games.iter().all(|game| game.won));
+        let total = |games: &[Game]| games.iter().map(|game| game.guesses.len()).sum::<usize>();
+        assert!(total(&games) <= total(&built_in));
+    }
+
+    #[test]
+    fn should_referee_external_solvers_through_the_protocol() {
+        let setup = Setup::new(&BenchOptions { words: Some("en".to_string()), ..BenchOptions::default() }).unwrap();
+        let mut manager = GameManager::new(History::in_memory(), Stats::in_memory());
+        manager.configure(&Config::default());
+
+        let mut output = Vec::new();
+        let mut input = "{\"guess\":\"zzzzz\"}\n{\"guess\":\"crane\"}\n".as_bytes();
+        let game = play_through_protocol(&mut manager, setup.game("crane"), &mut input, &mut output).unwrap();
+        assert_eq!(game, self::game("crane", true, &["crane"]));
+
+        let error = play_through_protocol(&mut manager, setup.game("crane"), &mut "".as_bytes(), &mut output).unwrap_err();
+        assert!(error.contains("stopped"), "{}", error);
+    }
+
+    #[test]
+    fn should_run_solvers_with_quoted_arguments() {
+        let setup = Setup::new(&BenchOptions { words: Some("en".to_string()), ..BenchOptions::default() }).unwrap();
+        let mut solver = External::spawn("read line; printf '{\"guess\": \"%s\"}\\n' crane; cat > /dev/null", &setup.language).unwrap();
+
+        assert_eq!(solver.play(setup.game("crane")), Ok(game("crane", true, &["crane"])));
+    }
+
+    #[test]
+    fn should_summarise_and_export_games() {
+        let games = [game("crane", true, &["trace", "crane"]), game("cable", false, &["a", "b", "c"]), game("brave", true, &["brave"])];
+        let report = Report::new("test", "en", 5, 3, false, &games);
+
+        assert_eq!((report.played, report.won, report.average), (3, 2, 1.5));
+        assert_eq!(report.distribution, vec![1, 1, 0]);
+        assert_eq!(report.failures, vec!["cable"]);
+        assert_eq!(report.worst()[0].answer, "cable");
+        assert_eq!(report.csv().lines().nth(1), Some("crane,true,2,trace crane"));
+
+        let json: serde_json::Value = serde_json::from_str(&serde_json::to_string(&report).unwrap()).unwrap();
+        assert_eq!(json["games"][2]["guesses"], serde_json::json!(["brave"]));
+        assert_eq!(json["average"], 1.5);
+    }
+}
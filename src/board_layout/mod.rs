@@ -0,0 +1,136 @@
+use ratatui::layout::Rect;
+
+const TILE_HEIGHTS: std::ops::RangeInclusive<u16> = 3..=6;
+const TILE_GAP: u16 = 2;
+// Rows above and below the board: the toast row, the gap above the keyboard, its title and bottom padding.
+const CHROME_HEIGHT: u16 = 4;
+
+// Size of the tiles of a grid and the space between them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Tiles {
+    pub width: u16,
+    pub height: u16,
+    pub gap: u16,
+    pub row_gap: u16
+}
+
+impl Tiles {
+
+    pub const COMPACT: Tiles = Tiles { width: 1, height: 1, gap: 1, row_gap: 0 };
+    const SMALL_KEYS: Tiles = Tiles { width: 3, height: 1, gap: 1, row_gap: 0 };
+    const LARGE_KEYS: Tiles = Tiles { width: 5, height: 3, gap: 1, row_gap: 1 };
+
+    // Tiles about as wide as they're high, given terminal cells are roughly twice as high as wide.
+    fn square(height: u16) -> Self {
+        Self { width: height * 2 + 1, height, gap: TILE_GAP, row_gap: 1 }
+    }
+
+    pub fn width(&self, columns: usize) -> u16 {
+        let columns = columns as u16;
+        columns * self.width + columns.saturating_sub(1) * self.gap
+    }
+
+    pub fn height(&self, rows: usize) -> u16 {
+        let rows = rows as u16;
+        rows * self.height + rows.saturating_sub(1) * self.row_gap
+    }
+
+    // Wide tiles take the place of two tiles and the gap between them.
+    pub fn wide_width(&self) -> u16 {
+        self.width * 2 + self.gap
+    }
+
+    // Areas of the tiles in the given row of the grid, centred horizontally in `area`.
+    pub fn row(&self, area: Rect, row: usize, columns: usize) -> Vec<Rect> {
+        self.row_of(area, row, &vec![self.width; columns])
+    }
+
+    // Like `row`, for tiles of the given widths.
+    pub fn row_of(&self, area: Rect, row: usize, widths: &[u16]) -> Vec<Rect> {
+        let y = area.y + row as u16 * (self.height + self.row_gap);
+        let total = widths.iter().sum::<u16>() + widths.len().saturating_sub(1) as u16 * self.gap;
+        let mut x = area.x + area.width.saturating_sub(total) / 2;
+
+        widths.iter().map(|width| {
+            let tile = Rect::new(x, y, *width, self.height).intersection(area);
+            x += width + self.gap;
+            tile
+        }).collect()
+    }
+}
+
+// The biggest board and keyboard fitting the terminal, kept in proportion on large ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoardLayout {
+    pub board: Tiles,
+    pub keys: Tiles,
+    word_len: usize,
+    max_tries: usize,
+    key_rows: usize
+}
+
+impl BoardLayout {
+
+    pub fn fit(area: Rect, word_len: usize, max_tries: usize, key_rows: usize, key_len: usize) -> Option<Self> {
+        let candidates = TILE_HEIGHTS.rev()
+            .flat_map(|height| [(Tiles::square(height), Tiles::LARGE_KEYS), (Tiles::square(height), Tiles::SMALL_KEYS)])
+            .chain([(Tiles::COMPACT, Tiles::COMPACT)]);
+
+        candidates
+            .map(|(board, keys)| BoardLayout { board, keys, word_len, max_tries, key_rows })
+            .find(|layout| {
+                let (width, height) = layout.size(key_len);
+                width <= area.width && height <= area.height
+            })
+    }
+
+    // The smallest terminal the compact layout fits in.
+    pub fn min_size(word_len: usize, max_tries: usize, key_rows: usize, key_len: usize) -> (u16, u16) {
+        BoardLayout { board: Tiles::COMPACT, keys: Tiles::COMPACT, word_len, max_tries, key_rows }.size(key_len)
+    }
+
+    fn size(&self, key_len: usize) -> (u16, u16) {
+        let width = self.board.width(self.word_len).max(self.keys.width(key_len));
+        (width, self.board_height() + self.keys.height(self.key_rows) + CHROME_HEIGHT)
+    }
+
+    pub fn board_height(&self) -> u16 {
+        self.board.height(self.max_tries)
+    }
+
+    pub fn keyboard_height(&self) -> u16 {
+        self.keys.height(self.key_rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+    use crate::board_layout::{BoardLayout, Tiles};
+
+    #[test]
+    fn should_use_biggest_tiles_that_fit() {
+        let layout = BoardLayout::fit(Rect::new(0, 0, 200, 80), 5, 6, 3, 10).unwrap();
+        assert_eq!(layout.board.height, 6);
+
+        let layout = BoardLayout::fit(Rect::new(0, 0, 80, 40), 5, 6, 3, 10).unwrap();
+        assert_eq!(layout.board.height, 4);
+        assert!(layout.board_height() + layout.keyboard_height() <= 36);
+    }
+
+    #[test]
+    fn should_fall_back_to_compact_tiles_and_then_give_up() {
+        let layout = BoardLayout::fit(Rect::new(0, 0, 30, 14), 5, 6, 3, 10).unwrap();
+        assert_eq!(layout.board, Tiles::COMPACT);
+
+        assert_eq!(BoardLayout::min_size(5, 6, 3, 10), (19, 13));
+        assert_eq!(BoardLayout::fit(Rect::new(0, 0, 18, 14), 5, 6, 3, 10), None);
+    }
+
+    #[test]
+    fn should_centre_rows() {
+        let tiles = Tiles { width: 3, height: 1, gap: 1, row_gap: 1 };
+        let row = tiles.row(Rect::new(0, 0, 20, 10), 2, 3);
+        assert_eq!(row, vec![Rect::new(4, 4, 3, 1), Rect::new(8, 4, 3, 1), Rect::new(12, 4, 3, 1)]);
+    }
+}
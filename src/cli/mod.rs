@@ -0,0 +1,409 @@
+use std::str::FromStr;
+use crate::bench::BenchOptions;
+use crate::config::Config;
+use crate::decision_tree::DecisionTree;
+use crate::guess::score;
+use crate::language::Language;
+use crate::letters::LetterState;
+use crate::protocol::PROTOCOLS;
+use crate::settings::{DEFAULT_TRIES, DEFAULT_WORD_LENGTH};
+use crate::solver::{parse_feedback, Solver};
+use crate::stats::{GameRecord, Stats};
+use crate::words::WordPack;
+
+pub const USAGE: &str = "Usage: wordle [play] [options]
+       wordle stats
+       wordle share
+       wordle solve [--words <pack>] [--lang en|pl] [--length <n>] [--tree [--hard]] [<guess> <colours>]...
+       wordle words <command>
+       wordle bench [options]
+       wordle --version | --help
+
+Commands:
+  play                      Play in the terminal, the default
+  stats                     Print win rates and guess distributions
+  share                     Print the colours of the last game, without its letters
+  solve                     List the words matching guesses so far and suggest the next one
+  words                     Manage word packs, see `wordle words help`
+  bench                     Measure a solver on every answer, see `wordle bench --help`
+
+Play options:
+  --length <n>              Word length
+  --tries <n>               Number of tries
+  --hard                    Hard mode, every revealed hint has to be used
+  --mode easy|normal|hard   How common the answers are
+  --seed <n>                Pick the same words on every run with this seed (needs a word pack)
+  --words <pack>            Pick answers from a word pack instead of the online API
+  --lang en|pl              Language of the words, keyboard and messages
+  --theme <theme>           Colour theme
+  --keyboard <layout>       On-screen keyboard layout
+  --repeat-window <games>   Games to wait before an answer can repeat
+  --no-animations           Turn animations off
+  --accessible              Mark colours inside tiles, describe the last guess in text and
+                            turn animations off
+  --plain                   Play line by line, reading guesses from stdin and printing each
+                            letter's colour; the default when not run in a terminal
+  --protocol jsonl          Let a program play: new games, feedback and results are written
+                            as JSON lines and guesses read as {\"guess\":\"crane\"} lines
+
+Colours given to solve have one letter per tile: g green, y yellow and . grey. With --tree the
+suggestion follows a decision tree taking the fewest guesses on average, built once per word list
+and then cached; --hard builds it for hard mode.";
+
+// How many matching words solve lists.
+const SHOWN_CANDIDATES: usize = 20;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Play(Box<PlayOptions>),
+    Stats,
+    Share,
+    Solve(SolveOptions),
+    Words(Vec<String>),
+    Bench(BenchOptions),
+    Version,
+    Help
+}
+
+// Command line options override the config for this session only.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PlayOptions {
+    pub overrides: Config,
+    pub seed: Option<u64>,
+    pub plain: bool,
+    pub protocol: Option<String>
+}
+
+impl PlayOptions {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = PlayOptions::default();
+        let config = &mut options.overrides;
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--length" => config.word_length = Some(number(&mut args, arg)?),
+                "--tries" => config.tries = Some(number(&mut args, arg)?),
+                "--hard" => config.hard_mode = Some(true),
+                // --difficulty is what this option was called before.
+                "--mode" | "--difficulty" => config.difficulty = Some(value(&mut args, arg)?),
+                "--seed" => options.seed = Some(number(&mut args, arg)?),
+                "--words" => config.words = Some(value(&mut args, arg)?),
+                "--lang" => config.language = Some(value(&mut args, arg)?),
+                "--theme" => config.theme = Some(value(&mut args, arg)?),
+                "--keyboard" => config.keyboard = Some(value(&mut args, arg)?),
+                "--repeat-window" => config.repeat_window = Some(number(&mut args, arg)?),
+                "--no-animations" => config.animations = Some(false),
+                "--plain" => options.plain = true,
+                "--accessible" => config.accessible = Some(true),
+                "--protocol" => {
+                    let protocol = value(&mut args, arg)?;
+                    if !PROTOCOLS.contains(&protocol.as_str()) {
+                        return Err(format!("Unknown protocol '{}', expected {}", protocol, PROTOCOLS.join(" or ")));
+                    }
+                    options.protocol = Some(protocol);
+                },
+                other => return Err(format!("Unknown option '{}'\n\n{}", other, USAGE))
+            }
+        }
+
+        Ok(options)
+    }
+
+    pub fn apply(&self, config: &mut Config) {
+        let overrides = self.overrides.clone();
+        config.word_length = overrides.word_length.or(config.word_length);
+        config.tries = overrides.tries.or(config.tries);
+        config.hard_mode = overrides.hard_mode.or(config.hard_mode);
+        config.difficulty = overrides.difficulty.or(config.difficulty.take());
+        config.words = overrides.words.or(config.words.take());
+        config.language = overrides.language.or(config.language.take());
+        config.theme = overrides.theme.or(config.theme.take());
+        config.keyboard = overrides.keyboard.or(config.keyboard.take());
+        config.repeat_window = overrides.repeat_window.or(config.repeat_window);
+        config.animations = overrides.animations.or(config.animations);
+        config.accessible = overrides.accessible.or(config.accessible);
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SolveOptions {
+    pub words: Option<String>,
+    pub language: Option<String>,
+    pub length: Option<usize>,
+    pub guesses: Vec<(String, Vec<LetterState>)>,
+    pub tree: bool,
+    pub hard: bool
+}
+
+impl SolveOptions {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = SolveOptions::default();
+        let mut words = Vec::new();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--words" => options.words = Some(value(&mut args, arg)?),
+                "--lang" => options.language = Some(value(&mut args, arg)?),
+                "--length" => options.length = Some(number(&mut args, arg)?),
+                "--tree" => options.tree = true,
+                "--hard" => options.hard = true,
+                flag if flag.starts_with("--") => return Err(format!("Unknown option '{}'", flag)),
+                word => words.push(word.to_lowercase())
+            }
+        }
+
+        if options.hard && !options.tree {
+            return Err("--hard only applies to --tree".to_string());
+        }
+
+        if words.len() % 2 != 0 {
+            return Err("Expected a guess followed by its colours, e.g. `wordle solve crane ..gy.`".to_string());
+        }
+
+        for pair in words.chunks(2) {
+            let (guess, colours) = (&pair[0], &pair[1]);
+            let feedback = parse_feedback(colours)?;
+            if feedback.len() != guess.chars().count() {
+                return Err(format!("'{}' has {} colours for {} letters", colours, feedback.len(), guess.chars().count()));
+            }
+            options.guesses.push((guess.clone(), feedback));
+        }
+
+        Ok(options)
+    }
+}
+
+fn value<'a>(args: &mut impl Iterator<Item = &'a String>, flag: &str) -> Result<String, String> {
+    args.next().cloned().ok_or_else(|| format!("Missing value for {}", flag))
+}
+
+fn number<'a, T: FromStr>(args: &mut impl Iterator<Item = &'a String>, flag: &str) -> Result<T, String> {
+    let value = value(args, flag)?;
+    value.parse().map_err(|_| format!("Invalid value '{}' for {}", value, flag))
+}
+
+// Without a command the options are for `play`.
+pub fn parse(args: &[String]) -> Result<Command, String> {
+    let Some((command, rest)) = args.split_first() else {
+        return Ok(Command::Play(Box::default()));
+    };
+
+    let no_options = |command: Command| match rest.first() {
+        Some(arg) => Err(format!("Unexpected argument '{}'\n\n{}", arg, USAGE)),
+        None => Ok(command)
+    };
+
+    match command.as_str() {
+        "play" => PlayOptions::parse(rest).map(|options| Command::Play(Box::new(options))),
+        "stats" => no_options(Command::Stats),
+        "share" => no_options(Command::Share),
+        "solve" => SolveOptions::parse(rest).map(Command::Solve),
+        "words" => Ok(Command::Words(rest.to_vec())),
+        "bench" => BenchOptions::parse(rest).map(Command::Bench),
+        "--version" | "-V" => Ok(Command::Version),
+        "--help" | "-h" | "help" => Ok(Command::Help),
+        option if option.starts_with('-') => PlayOptions::parse(args).map(|options| Command::Play(Box::new(options))),
+        other => Err(format!("Unknown command '{}'\n\n{}", other, USAGE))
+    }
+}
+
+pub fn version() -> String {
+    format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+}
+
+pub fn stats(stats: &Stats) -> Result<(), String> {
+    let summaries = stats.by_difficulty();
+    if summaries.is_empty() {
+        println!("No games played yet.");
+        return Ok(());
+    }
+
+    println!("{:<12} {:>8} {:>8} {:>8}", "Difficulty", "Played", "Won", "Win %");
+    for (difficulty, summary) in &summaries {
+        println!("{:<12} {:>8} {:>8} {:>8}", difficulty.to_string(), summary.played, summary.won, summary.win_rate());
+    }
+
+    for difficulty in summaries.keys() {
+        let distribution = stats.distribution(*difficulty);
+        let most = distribution.values().copied().max().unwrap_or(0);
+        if most == 0 {
+            continue;
+        }
+
+        println!("\nGuess distribution ({}):", difficulty);
+        for (guesses, count) in distribution {
+            println!("{:>3} {} {}", guesses, "█".repeat((count * 30).div_ceil(most)), count);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn share(stats: &Stats) -> Result<(), String> {
+    let record = stats.last().ok_or("No games played yet")?;
+    if record.words.is_empty() {
+        return Err("The last game was played before guesses were kept, so there's nothing to share".to_string());
+    }
+
+    println!("{}", share_text(record));
+    Ok(())
+}
+
+fn share_text(record: &GameRecord) -> String {
+    let guesses = if record.won { record.guesses.to_string() } else { "X".to_string() };
+    let mut text = match record.tries {
+        Some(tries) => format!("Wordle {}/{} ({})\n", guesses, tries, record.difficulty),
+        None => format!("Wordle {} ({})\n", guesses, record.difficulty)
+    };
+
+    for word in &record.words {
+        text.push('\n');
+        text.extend(score(word, &record.answer).iter().map(|state| match state {
+            LetterState::RightPosition => '🟩',
+            LetterState::WrongPosition => '🟨',
+            LetterState::NotOccurring | LetterState::NotChecked => '⬛'
+        }));
+    }
+    text
+}
+
+pub fn solve(options: &SolveOptions) -> Result<(), String> {
+    let language = match &options.language {
+        Some(code) => Language::parse(code)?,
+        None => Language::english()
+    };
+    let pack = match &options.words {
+        Some(reference) => WordPack::resolve(reference)?,
+        None => WordPack::embedded(&language.pack).ok_or(format!("Missing embedded pack '{}'", language.pack))?
+    };
+    let length = options.length
+        .or(options.guesses.first().map(|(guess, _)| guess.chars().count()))
+        .unwrap_or(DEFAULT_WORD_LENGTH as usize);
+
+    let mut solver = Solver::new(pack.with_length(length).words());
+    for (guess, feedback) in &options.guesses {
+        if guess.chars().count() != length {
+            return Err(format!("'{}' doesn't have {} letters", guess, length));
+        }
+        solver.apply(guess, feedback);
+    }
+
+    let candidates = solver.candidates();
+    if candidates.is_empty() {
+        return Err(format!("No {}-letter word in '{}' matches these colours", length, pack.name()));
+    }
+
+    let shown: Vec<&str> = candidates.iter().take(SHOWN_CANDIDATES).map(String::as_str).collect();
+    let more = candidates.len().saturating_sub(SHOWN_CANDIDATES);
+    println!("{} matching words: {}{}", candidates.len(), shown.join(", "), if more > 0 { format!(" and {} more", more) } else { String::new() });
+
+    let tree = if options.tree {
+        Some(DecisionTree::load_or_build(pack.with_length(length).words(), DEFAULT_TRIES, options.hard)?)
+    } else {
+        None
+    };
+    let suggestion = match tree.as_ref().map(|tree| tree.next(&options.guesses)) {
+        Some(Some(guess)) => Some(guess),
+        Some(None) => {
+            println!("These guesses aren't in the decision tree, so the suggestion is a guess of its own.");
+            solver.suggest()
+        },
+        None => solver.suggest()
+    };
+    if let Some(suggestion) = suggestion {
+        println!("Try: {}", suggestion);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{parse, share_text, Command};
+    use crate::config::Config;
+    use crate::difficulty::Difficulty;
+    use crate::letters::LetterState;
+    use crate::stats::GameRecord;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn should_parse_play_options_with_or_without_command() {
+        let Ok(Command::Play(options)) = parse(&args("play --length 6 --tries 8 --hard --mode hard --seed 42 --words en --lang pl --theme light")) else {
+            panic!("Expected play options");
+        };
+        assert_eq!(options.seed, Some(42));
+        assert!(!options.plain);
+        assert_eq!(options.overrides, Config {
+            word_length: Some(6),
+            tries: Some(8),
+            hard_mode: Some(true),
+            difficulty: Some("hard".to_string()),
+            words: Some("en".to_string()),
+            language: Some("pl".to_string()),
+            theme: Some("light".to_string()),
+            ..Config::default()
+        });
+
+        assert_eq!(parse(&args("--no-animations --plain")), parse(&args("play --no-animations --plain")));
+        assert!(matches!(parse(&args("--protocol jsonl")), Ok(Command::Play(options)) if options.protocol.as_deref() == Some("jsonl")));
+        assert!(parse(&args("--protocol xml")).is_err());
+        assert_eq!(parse(&[]), Ok(Command::Play(Box::default())));
+    }
+
+    #[test]
+    fn should_override_only_given_options() {
+        let mut config = Config { word_length: Some(4), theme: Some("dark".to_string()), ..Config::default() };
+        let Ok(Command::Play(options)) = parse(&args("--theme light")) else {
+            panic!("Expected play options");
+        };
+        options.apply(&mut config);
+
+        assert_eq!(config.word_length, Some(4));
+        assert_eq!(config.theme.as_deref(), Some("light"));
+    }
+
+    #[test]
+    fn should_reject_bad_arguments() {
+        assert!(parse(&args("play --length five")).is_err());
+        assert!(parse(&args("play --tries")).is_err());
+        assert!(parse(&args("stats --all")).is_err());
+        assert!(parse(&args("dance")).is_err());
+        assert!(parse(&args("solve crane")).is_err());
+        assert!(parse(&args("solve crane gy")).is_err());
+        assert_eq!(parse(&args("--version")), Ok(Command::Version));
+        assert_eq!(parse(&args("--help")), Ok(Command::Help));
+    }
+
+    #[test]
+    fn should_parse_solve_guesses() {
+        let Ok(Command::Solve(options)) = parse(&args("solve --words en --tree CRANE ..gy. trace gg...")) else {
+            panic!("Expected solve options");
+        };
+        assert_eq!(options.words.as_deref(), Some("en"));
+        assert!(options.tree && !options.hard);
+        assert_eq!(options.guesses.len(), 2);
+        assert_eq!(options.guesses[0].0, "crane");
+        assert_eq!(options.guesses[0].1[2], LetterState::RightPosition);
+        assert!(parse(&args("solve --hard crane ..gy.")).is_err());
+    }
+
+    #[test]
+    fn should_share_colours_without_letters() {
+        let record = GameRecord {
+            difficulty: Difficulty::Normal,
+            won: true,
+            guesses: 2,
+            tries: Some(6),
+            answer: "crane".to_string(),
+            words: vec!["trace".to_string(), "crane".to_string()]
+        };
+
+        assert_eq!(share_text(&record), "Wordle 2/6 (normal)\n\n⬛🟩🟩🟨🟩\n🟩🟩🟩🟩🟩");
+        assert_eq!(share_text(&GameRecord { won: false, tries: None, ..record }).lines().next(), Some("Wordle X (normal)"));
+    }
+}
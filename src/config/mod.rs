@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use toml_edit::DocumentMut;
+use crate::key_bindings::KeysConfig;
+use crate::paths;
+use crate::theme::ThemeConfig;
+
+const CONFIG_FILE: &str = "config.toml";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub word_length: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tries: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hard_mode: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    // "api" or a word pack reference.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub words: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repeat_window: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keyboard: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub animations: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bell: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accessible: Option<bool>,
+    #[serde(skip_serializing_if = "KeysConfig::is_empty")]
+    pub keys: KeysConfig,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub layouts: BTreeMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub themes: BTreeMap<String, ThemeConfig>
+}
+
+impl Config {
+
+    pub fn path() -> Option<PathBuf> {
+        paths::config_dir().map(|dir| dir.join(CONFIG_FILE))
+    }
+
+    pub fn load() -> Result<Self, String> {
+        let path = match Config::path() {
+            Some(path) => path,
+            None => return Ok(Config::default())
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => Config::parse(&contents).map_err(|e| format!("Invalid config '{}': {}", path.display(), e)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(format!("Failed to read '{}': {}", path.display(), e))
+        }
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        toml::from_str(contents).map_err(|e| e.message().to_string())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = Config::path().ok_or("Can't find the config directory")?;
+        let contents = self.update(&fs::read_to_string(&path).unwrap_or_default())?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("Failed to create '{}': {}", dir.display(), e))?;
+        }
+        fs::write(&path, contents).map_err(|e| format!("Failed to write '{}': {}", path.display(), e))
+    }
+
+    // Changes only the settings that differ from the file, so the user's comments and order stay.
+    // A file that isn't a valid config is written from scratch.
+    fn update(&self, contents: &str) -> Result<String, String> {
+        let (Ok(mut document), Ok(saved)) = (contents.parse::<DocumentMut>(), Config::parse(contents)) else {
+            return toml::to_string_pretty(self).map_err(|e| e.to_string());
+        };
+        let before = toml::Table::try_from(&saved).map_err(|e| e.to_string())?;
+        let after = toml::Table::try_from(self).map_err(|e| e.to_string())?;
+        let values = toml::to_string_pretty(self).map_err(|e| e.to_string())?.parse::<DocumentMut>().map_err(|e| e.to_string())?;
+
+        for key in before.keys().chain(after.keys()) {
+            if before.get(key) == after.get(key) {
+                continue;
+            }
+            match values.get(key) {
+                Some(item) => {
+                    let mut item = item.clone();
+                    // Keeps the comment after a changed value.
+                    if let (Some(old), Some(new)) = (document.get(key).and_then(|item| item.as_value()), item.as_value_mut()) {
+                        *new.decor_mut() = old.decor().clone();
+                    }
+                    match document.get_mut(key) {
+                        Some(slot) => *slot = item,
+                        None => {
+                            document.insert(key, item);
+                        }
+                    }
+                },
+                None => {
+                    document.remove(key);
+                }
+            }
+        }
+        Ok(document.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Config;
+
+    #[test]
+    fn should_parse_user_layouts() {
+        let config = Config::parse("keyboard = \"team\"\n[layouts]\nteam = [\"abc\", \"def\"]\n").unwrap();
+        assert_eq!(config.keyboard.as_deref(), Some("team"));
+        assert_eq!(config.layouts.get("team"), Some(&vec!["abc".to_string(), "def".to_string()]));
+    }
+
+    #[test]
+    fn should_parse_user_themes() {
+        let config = Config::parse("theme = \"team\"\n[themes.team]\nbase = \"light\"\nwon = { fg = \"#00ff00\" }\n").unwrap();
+        let team = config.themes.get("team").unwrap();
+        assert_eq!(team.base.as_deref(), Some("light"));
+        assert_eq!(team.won.as_ref().and_then(|won| won.fg.as_deref()), Some("#00ff00"));
+    }
+
+    #[test]
+    fn should_reject_unknown_keys() {
+        assert!(Config::parse("keybord = \"dvorak\"").is_err());
+    }
+
+    #[test]
+    fn should_write_back_what_it_reads() {
+        let contents = "word_length = 6\ntries = 7\nhard_mode = true\nwords = \"pl\"\n\n[keys]\nquit = \"ctrl+q\"\n\n[layouts]\nteam = [\"abc\"]\n";
+        let config = Config::parse(contents).unwrap();
+        assert_eq!(Config::parse(&toml::to_string_pretty(&config).unwrap()), Ok(config));
+    }
+
+    #[test]
+    fn should_only_change_edited_settings_and_keep_comments() {
+        let contents = "# My settings\nword_length = 6  # longer words\ntries = 7\nbell = true\n\n[keys]\nquit = \"ctrl+q\"\n";
+        let mut config = Config::parse(contents).unwrap();
+        config.word_length = Some(7);
+        config.bell = None;
+        config.theme = Some("light".to_string());
+
+        let updated = config.update(contents).unwrap();
+        assert!(updated.starts_with("# My settings\nword_length = 7  # longer words\ntries = 7\n"), "{}", updated);
+        assert!(!updated.contains("bell"), "{}", updated);
+        assert_eq!(Config::parse(&updated), Ok(config));
+    }
+}
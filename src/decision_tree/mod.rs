@@ -0,0 +1,378 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use crate::guess::score;
+use crate::letters::LetterState;
+use crate::paths;
+use crate::solver::{format_feedback, pattern};
+
+// Bump when the search or the file format changes, so old trees are rebuilt.
+const VERSION: u32 = 1;
+// The search is exact and its time grows steeply with the list: up to a few seconds for this many words.
+pub const MAX_WORDS: usize = 400;
+// Colours of the longest words still fit a u32, three per letter.
+pub const MAX_LENGTH: usize = 20;
+
+// What to guess for every answer still possible: the guess of a node, then the
+// node below for each colouring of it. Guessing the answer has no node below.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecisionTree {
+    guess: String,
+    branches: BTreeMap<String, DecisionTree>
+}
+
+impl DecisionTree {
+    // The tree taking the fewest guesses in total over all the words, each one
+    // equally likely to be the answer. Fails when some word can't be found within
+    // the tries. Guesses are words of the list, as the game only accepts those.
+    pub fn build(words: &[String], tries: u8, hard: bool) -> Result<Self, String> {
+        let words: Vec<String> = words.iter().cloned().collect::<BTreeSet<_>>().into_iter().collect();
+        Self::check_size(&words)?;
+
+        let candidates: Vec<u16> = (0..words.len() as u16).collect();
+        let mut search = Search::new(&words, hard);
+        let hints = Hints::default();
+
+        let mode = if hard { "hard" } else { "normal" };
+        search.cost(&candidates, tries, &hints)
+            .ok_or(format!("Not every word can be found within {} tries in {} mode", tries, mode))?;
+        Ok(search.tree(&candidates, tries, &hints))
+    }
+
+    fn check_size(words: &[String]) -> Result<(), String> {
+        if words.len() > MAX_WORDS {
+            return Err(format!("Decision trees are limited to {} words, this list has {}", MAX_WORDS, words.len()));
+        }
+        if words.iter().any(|word| word.chars().count() > MAX_LENGTH) {
+            return Err(format!("Decision trees are limited to words of up to {} letters", MAX_LENGTH));
+        }
+        Ok(())
+    }
+
+    // Trees are slow to build for long lists, so they're kept in the cache directory.
+    // A cached tree that doesn't find every word, e.g. a cut off file, is built again.
+    pub fn load_or_build(words: &[String], tries: u8, hard: bool) -> Result<Self, String> {
+        let words: Vec<String> = words.iter().cloned().collect::<BTreeSet<_>>().into_iter().collect();
+        let path = Self::path(&words, tries, hard);
+        if let Some(tree) = path.as_ref().and_then(|path| fs::read_to_string(path).ok()).and_then(|contents| Self::parse(&contents)) {
+            if tree.solves(&words, tries) {
+                return Ok(tree);
+            }
+        }
+
+        Self::check_size(&words)?;
+        eprintln!("Building the decision tree for {} words, later runs use the cached one.", words.len());
+        let tree = Self::build(&words, tries, hard)?;
+        if let Some(path) = path {
+            let mode = if hard { "hard" } else { "normal" };
+            let header = format!("# {} words, {} tries, {} mode\n", words.len(), tries, mode);
+            // Written next to the tree and moved over it, so an interrupted write never leaves half a tree.
+            let temp = path.with_extension(format!("{}.tmp", process::id()));
+            let _ = path.parent().map(fs::create_dir_all);
+            if fs::write(&temp, header + &tree.format()).and_then(|_| fs::rename(&temp, &path)).is_err() {
+                let _ = fs::remove_file(&temp);
+            }
+        }
+        Ok(tree)
+    }
+
+    // Whether following the tree finds every word within the tries.
+    fn solves(&self, words: &[String], tries: u8) -> bool {
+        words.iter().all(|answer| {
+            let mut node = Some(self);
+            for _ in 0..tries {
+                match node {
+                    Some(current) if current.guess == *answer => return true,
+                    Some(current) => node = current.after(&score(&current.guess, answer)),
+                    None => return false
+                }
+            }
+            false
+        })
+    }
+
+    // Named after the words and rules, so a changed list gets its own tree.
+    fn path(words: &[String], tries: u8, hard: bool) -> Option<PathBuf> {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        let key = format!("{} {} {} {}", VERSION, tries, hard, words.join(" "));
+        for byte in key.bytes() {
+            hash = (hash ^ byte as u64).wrapping_mul(0x100000001b3);
+        }
+        paths::trees_dir().map(|dir| dir.join(format!("{:016x}.txt", hash)))
+    }
+
+    pub fn guess(&self) -> &str {
+        &self.guess
+    }
+
+    // The node reached by following the colours of a guess, none when it was the answer.
+    pub fn after(&self, feedback: &[LetterState]) -> Option<&DecisionTree> {
+        self.branches.get(&format_feedback(feedback))
+    }
+
+    // The next guess after the given ones, none when they left the tree.
+    pub fn next(&self, guesses: &[(String, Vec<LetterState>)]) -> Option<&str> {
+        let mut node = self;
+        for (guess, feedback) in guesses {
+            if *guess != node.guess {
+                return None;
+            }
+            node = node.after(feedback)?;
+        }
+        Some(&node.guess)
+    }
+
+    // One line per node: the colours leading to it, then its guess.
+    fn format(&self) -> String {
+        let mut lines = String::new();
+        self.write_lines(&mut Vec::new(), &mut lines);
+        lines
+    }
+
+    fn write_lines<'a>(&'a self, path: &mut Vec<&'a str>, lines: &mut String) {
+        path.push(&self.guess);
+        *lines += &path.join(" ");
+        lines.push('\n');
+        path.pop();
+
+        for (feedback, branch) in &self.branches {
+            path.push(feedback);
+            branch.write_lines(path, lines);
+            path.pop();
+        }
+    }
+
+    fn parse(contents: &str) -> Option<Self> {
+        let mut lines = contents.lines().filter(|line| !line.starts_with('#') && !line.trim().is_empty());
+        let mut root = Self::leaf(lines.next()?.trim());
+
+        for line in lines {
+            let mut fields: Vec<&str> = line.split_whitespace().collect();
+            let guess = fields.pop()?;
+            let (last, path) = fields.split_last()?;
+
+            let mut node = &mut root;
+            for feedback in path {
+                node = node.branches.get_mut(*feedback)?;
+            }
+            node.branches.insert(last.to_string(), Self::leaf(guess));
+        }
+
+        Some(root)
+    }
+
+    fn leaf(guess: &str) -> Self {
+        Self { guess: guess.to_string(), branches: BTreeMap::new() }
+    }
+}
+
+// Letters hard mode makes every later guess use: greens in their place and yellows anywhere.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+struct Hints {
+    greens: BTreeMap<usize, char>,
+    yellows: BTreeSet<char>
+}
+
+impl Hints {
+    fn allow(&self, word: &[char]) -> bool {
+        self.greens.iter().all(|(i, letter)| word[*i] == *letter) && self.yellows.iter().all(|letter| word.contains(letter))
+    }
+
+    fn with(&self, word: &[char], pattern: u32) -> Self {
+        let mut hints = self.clone();
+        let mut digits = pattern;
+        for i in (0..word.len()).rev() {
+            match digits % 3 {
+                2 => { hints.greens.insert(i, word[i]); },
+                1 => { hints.yellows.insert(word[i]); },
+                _ => {}
+            }
+            digits /= 3;
+        }
+        hints
+    }
+}
+
+// The fewest guesses over a group of answers with this many tries left, with
+// the guess achieving it.
+type Best = Option<(u32, u16)>;
+
+// Answers by the colours a guess gives them.
+type Groups = Vec<(u32, Vec<u16>)>;
+
+// Words are referred to by their index in the sorted list, which MAX_WORDS keeps within a u16.
+struct Search<'a> {
+    words: &'a [String],
+    letters: Vec<Vec<char>>,
+    // Colours of every guess against every answer, by guess * words + answer.
+    patterns: Vec<u32>,
+    solved: u32,
+    hard: bool,
+    memo: HashMap<(Vec<u16>, u8, Hints), Best>
+}
+
+impl<'a> Search<'a> {
+    fn new(words: &'a [String], hard: bool) -> Self {
+        let patterns = words.iter()
+            .flat_map(|guess| words.iter().map(move |answer| pattern(guess, answer) as u32))
+            .collect();
+        let length = words.first().map_or(0, |word| word.chars().count());
+
+        Self {
+            words,
+            letters: words.iter().map(|word| word.chars().collect()).collect(),
+            patterns,
+            solved: 3u32.pow(length as u32) - 1,
+            hard,
+            memo: HashMap::new()
+        }
+    }
+
+    // Answers grouped by the colours the guess would give them, leaving out the guess itself.
+    fn split(&self, guess: u16, candidates: &[u16]) -> Groups {
+        let row = guess as usize * self.words.len();
+        let mut groups: BTreeMap<u32, Vec<u16>> = BTreeMap::new();
+        for &answer in candidates {
+            let pattern = self.patterns[row + answer as usize];
+            if pattern != self.solved {
+                groups.entry(pattern).or_default().push(answer);
+            }
+        }
+        groups.into_iter().collect()
+    }
+
+    // Every answer costs one guess per row it takes. A group of n needs at
+    // least 2n - 1 more: one guess can only be right for one of them.
+    fn cost(&mut self, candidates: &[u16], tries: u8, hints: &Hints) -> Best {
+        let count = candidates.len() as u32;
+        match (count, tries) {
+            (_, 0) => return None,
+            (1, _) => return Some((1, candidates[0])),
+            (_, 1) => return None,
+            (2, _) => return Some((3, candidates[0])),
+            _ => {}
+        }
+
+        let key = (candidates.to_vec(), tries, if self.hard { hints.clone() } else { Hints::default() });
+        if let Some(best) = self.memo.get(&key) {
+            return *best;
+        }
+
+        let mut options: Vec<(u32, u16, Groups)> = (0..self.words.len() as u16)
+            .filter(|guess| !self.hard || hints.allow(&self.letters[*guess as usize]))
+            .map(|guess| (guess, self.split(guess, candidates)))
+            .filter(|(_, groups)| groups.len() > 1 || groups.first().is_some_and(|(_, group)| group.len() < candidates.len()))
+            .filter(|(_, groups)| tries > 2 || groups.iter().all(|(_, group)| group.len() == 1))
+            .map(|(guess, groups)| (count + groups.iter().map(|(_, group)| 2 * group.len() as u32 - 1).sum::<u32>(), guess, groups))
+            .collect();
+        options.sort_by_key(|(bound, guess, _)| (*bound, *guess));
+
+        let mut best: Best = None;
+        for (bound, guess, groups) in options {
+            if best.is_some_and(|(cost, _)| bound >= cost) {
+                break;
+            }
+
+            let mut total = Some(bound);
+            for (pattern, group) in &groups {
+                let hints = if self.hard { hints.with(&self.letters[guess as usize], *pattern) } else { Hints::default() };
+                total = match (total, self.cost(group, tries - 1, &hints)) {
+                    (Some(total), Some((cost, _))) => Some(total + cost - (2 * group.len() as u32 - 1)),
+                    _ => None
+                };
+                if total.is_none() || best.is_some_and(|(cost, _)| total >= Some(cost)) {
+                    total = None;
+                    break;
+                }
+            }
+
+            if let Some(total) = total {
+                best = Some((total, guess));
+            }
+        }
+
+        self.memo.insert(key, best);
+        best
+    }
+
+    fn tree(&mut self, candidates: &[u16], tries: u8, hints: &Hints) -> DecisionTree {
+        let (_, guess) = self.cost(candidates, tries, hints).expect("a tree for a solvable group");
+        let mut tree = DecisionTree::leaf(&self.words[guess as usize]);
+
+        for (pattern, group) in self.split(guess, candidates) {
+            let hints = if self.hard { hints.with(&self.letters[guess as usize], pattern) } else { Hints::default() };
+            let feedback = format_feedback(&score(&self.words[guess as usize], &self.words[group[0] as usize]));
+            tree.branches.insert(feedback, self.tree(&group, tries - 1, &hints));
+        }
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decision_tree::{DecisionTree, MAX_WORDS};
+    use crate::guess::score;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    // Guesses the tree makes for an answer.
+    fn play(tree: &DecisionTree, answer: &str) -> Vec<String> {
+        let mut node = Some(tree);
+        let mut guesses = Vec::new();
+        while let Some(current) = node {
+            guesses.push(current.guess().to_string());
+            node = current.after(&score(current.guess(), answer));
+        }
+        guesses
+    }
+
+    #[test]
+    fn should_find_the_tree_with_fewest_guesses() {
+        let list = words(&["bill", "fill", "hill", "pill", "will", "bolt"]);
+        let tree = DecisionTree::build(&list, 6, false).unwrap();
+
+        let total: usize = list.iter().map(|answer| play(&tree, answer).len()).sum();
+        assert!(list.iter().all(|answer| play(&tree, answer).last() == Some(answer)));
+        // Whatever comes first, the four words ending in "ill" still have to be
+        // guessed one after another: 1 + 2 + (2 + 3 + 4 + 5).
+        assert_eq!(total, 17);
+    }
+
+    #[test]
+    fn should_respect_tries_and_hard_mode() {
+        let list = words(&["bill", "fill", "hill", "pill", "will"]);
+
+        assert!(DecisionTree::build(&list, 4, false).is_err());
+        let tree = DecisionTree::build(&list, 5, true).unwrap();
+        assert!(list.iter().all(|answer| play(&tree, answer).iter().all(|guess| guess.ends_with("ill"))));
+    }
+
+    #[test]
+    fn should_handle_the_longest_words_and_refuse_longer_ones() {
+        let list = words(&["abcdefghijklmnopqrst", "abcdefghijklmnopqrsu", "tsrqponmlkjihgfedcba"]);
+        let tree = DecisionTree::build(&list, 6, false).unwrap();
+        assert!(list.iter().all(|answer| play(&tree, answer).last() == Some(answer)));
+
+        assert!(DecisionTree::build(&words(&["abcdefghijklmnopqrstu", "abcdefghijklmnopqrstv"]), 6, false).is_err());
+        let many: Vec<String> = (0..=MAX_WORDS).map(|i| format!("{:04}", i)).collect();
+        assert!(DecisionTree::build(&many, 6, false).unwrap_err().contains(&MAX_WORDS.to_string()));
+    }
+
+    #[test]
+    fn should_round_trip_through_text() {
+        let list = words(&["crane", "trace", "cable", "brave", "crate", "grace"]);
+        let tree = DecisionTree::build(&list, 6, false).unwrap();
+
+        assert_eq!(DecisionTree::parse(&tree.format()), Some(tree.clone()));
+        assert!(tree.solves(&list, 6));
+        let cut_off: String = tree.format().lines().take(2).map(|line| format!("{}\n", line)).collect();
+        assert!(!DecisionTree::parse(&cut_off).unwrap().solves(&list, 6));
+        let first = tree.guess().to_string();
+        let feedback = score(&first, "cable");
+        assert_eq!(tree.next(&[(first.clone(), feedback.clone())]), tree.after(&feedback).map(DecisionTree::guess));
+        assert_eq!(tree.next(&[("zzzzz".to_string(), feedback)]), None);
+    }
+}
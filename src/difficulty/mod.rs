@@ -0,0 +1,93 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard
+}
+
+// Words differing in a single position, e.g. LIGHT, MIGHT and NIGHT for _IGHT.
+const TRICKY_NEIGHBOURS: usize = 3;
+
+impl Difficulty {
+
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    // Expects `ranked` to be ordered from the most to the least common word.
+    pub fn candidates(&self, ranked: &[String]) -> Vec<String> {
+        let third = ranked.len().div_ceil(3);
+
+        match self {
+            Difficulty::Easy => ranked.iter().take(third).cloned().collect(),
+            Difficulty::Normal => ranked.iter().take(third * 2).cloned().collect(),
+            Difficulty::Hard => ranked.iter().enumerate()
+                .filter(|(rank, word)| *rank >= third * 2 || has_double_letter(word) || is_tricky(word, ranked))
+                .map(|(_, word)| word.clone())
+                .collect()
+        }
+    }
+}
+
+fn has_double_letter(word: &str) -> bool {
+    let letters: Vec<char> = word.chars().collect();
+    letters.iter().enumerate().any(|(i, letter)| letters[i + 1..].contains(letter))
+}
+
+fn is_tricky(word: &str, words: &[String]) -> bool {
+    let neighbours = words.iter()
+        .filter(|other| {
+            other.chars().count() == word.chars().count() &&
+                other.chars().zip(word.chars()).filter(|(a, b)| a != b).count() == 1
+        })
+        .count();
+
+    neighbours >= TRICKY_NEIGHBOURS
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard"
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            other => Err(format!("Unknown difficulty '{}', expected easy, normal or hard", other))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::difficulty::Difficulty;
+
+    fn ranked() -> Vec<String> {
+        ["about", "other", "world", "light", "night", "plant", "might", "sight", "fuzzy"]
+            .iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn should_pick_common_words_on_easy() {
+        assert_eq!(Difficulty::Easy.candidates(&ranked()), vec!["about", "other", "world"]);
+    }
+
+    #[test]
+    fn should_pick_rare_words_double_letters_and_tricky_patterns_on_hard() {
+        let hard = Difficulty::Hard.candidates(&ranked());
+        assert_eq!(hard, vec!["light", "night", "might", "sight", "fuzzy"]);
+    }
+}
@@ -1,65 +1,166 @@
-use std::io;
-use crossterm::event;
-use crossterm::event::{Event, KeyCode};
-use crate::game_manager::{GameManager, GameManagerState};
-use crate::game_state::{GameStatus, GameUpdateResult};
-
-fn beep() {
-    println!("\x07")
+use std::io::{self, Write};
+use crate::action::{Action, Response};
+use crate::event_source::{AppEvent, EventSource};
+use crate::game_manager::GameManager;
+use crate::hit_areas::Target;
+
+// Written without a newline, which would move the cursor in raw mode.
+fn beep() -> io::Result<()> {
+    let mut stdout = io::stdout();
+    stdout.write_all(b"\x07")?;
+    stdout.flush()
+}
+
+// Draws, then waits for the next event, until the player quits or the source
+// runs dry.
+pub fn run<E, D>(manager: &mut GameManager, events: &mut E, mut draw: D) -> io::Result<()>
+where
+    E: EventSource,
+    D: FnMut(&mut GameManager) -> io::Result<()>
+{
+    loop {
+        draw(manager)?;
+
+        let Some(event) = events.next()? else {
+            return Ok(());
+        };
+
+        match handle_event(manager, event) {
+            Response::Quit => return Ok(()),
+            Response::Rejected if manager.bell() => beep()?,
+            _ => {}
+        }
+    }
+}
+
+pub fn handle_event(manager: &mut GameManager, event: AppEvent) -> Response {
+    let action = match event {
+        AppEvent::Key(key) => manager.key_bindings().action(&key, manager.typing()),
+        AppEvent::Click { column, row } => click(manager, column, row),
+        AppEvent::Tick => {
+            manager.tick();
+            None
+        },
+        // The next draw picks up the new size.
+        AppEvent::Resize(_, _) => None,
+        AppEvent::Terminate => return Response::Quit
+    };
+
+    action.map_or(Response::Continue, |action| manager.perform(action))
 }
 
-pub fn handle_events(manager: &mut GameManager) -> io::Result<bool> {
-
-    if event::poll(std::time::Duration::from_millis(50))? {
-        if let Event::Key(key) = event::read()? {
-            if key.kind == event::KeyEventKind::Press {
-                match &mut manager.state {
-                    GameManagerState::Playing(state) => {
-                        match &state.status {
-                            GameStatus::Won | GameStatus::Lost => {
-                                match key.code {
-                                    KeyCode::Enter => {
-                                        manager.restart();
-                                    },
-                                    KeyCode::Esc => return Ok(true),
-                                    _ => {}
-                                }
-                            },
-                            _ => {
-                                let correct_action = match key.code {
-                                    KeyCode::Esc => return Ok(true),
-                                    KeyCode::Backspace => state.pop_letter(),
-                                    KeyCode::Enter => state.commit_guess(),
-                                    KeyCode::Char(c) => state.push_letter(c.to_ascii_lowercase()),
-                                    _ => GameUpdateResult::Ok
-                                };
-
-                                if correct_action == GameUpdateResult::InvalidInput {
-                                    beep();
-                                }
-                            }
-
-                        }
-
-                    },
-                    GameManagerState::Loading => {
-                        match key.code {
-                            KeyCode::Esc => return Ok(true),
-                            _ => {}
-                        }
-                    }
-                    GameManagerState::Failure(_)  => {
-                        match key.code {
-                            KeyCode::Esc => return Ok(true),
-                            KeyCode::Enter => manager.restart(),
-                            _ => {}
-                        }
-                    }
-                }
-            }
+fn click(manager: &GameManager, column: u16, row: u16) -> Option<Action> {
+    match manager.hit_areas().target_at(column, row)? {
+        Target::Key(letter) => Some(Action::Type(letter)),
+        Target::Enter => Some(Action::Submit),
+        Target::Backspace => Some(Action::Delete),
+        Target::Entry(i) => Some(Action::Choose(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use crate::config::Config;
+    use crate::event_handler::run;
+    use crate::event_source::{AppEvent, ScriptedEvents};
+    use crate::game_manager::{GameManager, GameManagerState};
+    use crate::game_state::GameStatus;
+    use crate::hit_areas::Target;
+    use crate::history::History;
+    use crate::key_bindings::KeyCode;
+    use crate::stats::Stats;
+    use crate::ui::ui;
+
+    fn manager() -> GameManager {
+        let mut manager = GameManager::new(History::in_memory(), Stats::in_memory());
+        manager.configure(&Config { words: Some("en".to_string()), animations: Some(false), ..Config::default() });
+        manager.open_menu();
+        manager
+    }
+
+    fn play(manager: &mut GameManager, events: Vec<AppEvent>) {
+        run(manager, &mut ScriptedEvents::new(events), |_| Ok(())).unwrap();
+    }
+
+    fn pending(manager: &GameManager) -> String {
+        match &manager.state {
+            GameManagerState::Playing(state) => state.get(state.guess_count()).letters().iter().map(|letter| letter.value).collect(),
+            _ => panic!("Expected a game in progress")
         }
     }
 
+    #[test]
+    fn should_start_and_win_a_game_from_scripted_events() {
+        let mut manager = manager();
+        // New game, then start it with the default options.
+        play(&mut manager, vec![ScriptedEvents::key(KeyCode::Enter), ScriptedEvents::key(KeyCode::Enter)]);
+        assert!(manager.screen().is_none());
 
-    Ok(false)
-}
\ No newline at end of file
+        let target = match &manager.state {
+            GameManagerState::Playing(state) => state.target_word.clone(),
+            _ => panic!("Expected a game to start")
+        };
+
+        let mut events = ScriptedEvents::typing("zz");
+        events.push(ScriptedEvents::key(KeyCode::Backspace));
+        events.push(ScriptedEvents::key(KeyCode::Backspace));
+        events.extend(ScriptedEvents::typing(&target));
+        events.push(AppEvent::Tick);
+        events.push(ScriptedEvents::key(KeyCode::Enter));
+        play(&mut manager, events);
+
+        match &manager.state {
+            GameManagerState::Playing(state) => assert_eq!(state.status, GameStatus::Won),
+            _ => panic!("Expected the finished game to stay on screen")
+        }
+        assert_eq!(manager.stats().by_difficulty().values().map(|summary| summary.won).sum::<usize>(), 1);
+    }
+
+    #[test]
+    fn should_stop_on_quit() {
+        let mut manager = manager();
+        let mut events = ScriptedEvents::new(vec![ScriptedEvents::key(KeyCode::Esc), AppEvent::Tick]);
+        let mut draws = 0;
+
+        run(&mut manager, &mut events, |_| {
+            draws += 1;
+            Ok(())
+        }).unwrap();
+
+        assert_eq!(draws, 1);
+    }
+
+    #[test]
+    fn should_stop_when_terminated_mid_game() {
+        let mut manager = manager();
+        let mut events = vec![ScriptedEvents::key(KeyCode::Enter), ScriptedEvents::key(KeyCode::Enter), AppEvent::Terminate];
+        events.extend(ScriptedEvents::typing("ab"));
+        play(&mut manager, events);
+
+        assert_eq!(pending(&manager).trim(), "");
+        assert!(manager.saved_game().is_some());
+    }
+
+    #[test]
+    fn should_click_keys_drawn_on_the_last_frame() {
+        let mut manager = manager();
+        play(&mut manager, vec![ScriptedEvents::key(KeyCode::Enter), ScriptedEvents::key(KeyCode::Enter)]);
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 40)).unwrap();
+        let draw = |manager: &mut GameManager| -> io::Result<()> {
+            terminal.draw(|frame| ui(manager, frame))?;
+            Ok(())
+        };
+        run(&mut manager, &mut ScriptedEvents::new([AppEvent::Resize(80, 40)]), draw).unwrap();
+
+        let (column, row) = (0..40).flat_map(|row| (0..80).map(move |column| (column, row)))
+            .find(|&(column, row)| manager.hit_areas().target_at(column, row) == Some(Target::Key('q')))
+            .expect("Expected the keyboard to be drawn");
+        play(&mut manager, vec![AppEvent::Click { column, row }]);
+
+        assert_eq!(pending(&manager).trim(), "q");
+    }
+}
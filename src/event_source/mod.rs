@@ -0,0 +1,147 @@
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use crossterm::event;
+use crossterm::event::{Event, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
+use signal_hook::consts::{SIGINT, SIGTERM};
+use crate::key_bindings::{Key, KeyCode, Modifiers};
+
+// How often a tick drives animations and toasts, whether or not input arrives.
+const TICK: Duration = Duration::from_millis(50);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AppEvent {
+    Key(Key),
+    Click { column: u16, row: u16 },
+    Tick,
+    Resize(u16, u16),
+    // SIGINT or SIGTERM, which end the game like quitting does.
+    Terminate
+}
+
+// Yields the events the game is driven by. `None` means there are no more,
+// e.g. a script that has run out.
+pub trait EventSource {
+    fn next(&mut self) -> io::Result<Option<AppEvent>>;
+}
+
+pub struct CrosstermEvents {
+    terminated: Arc<AtomicBool>,
+    last_tick: Instant
+}
+
+impl CrosstermEvents {
+    // Signals only set a flag, which is checked between polls.
+    pub fn new() -> io::Result<Self> {
+        let terminated = Arc::new(AtomicBool::new(false));
+        signal_hook::flag::register(SIGINT, Arc::clone(&terminated))?;
+        signal_hook::flag::register(SIGTERM, Arc::clone(&terminated))?;
+        Ok(Self { terminated, last_tick: Instant::now() })
+    }
+}
+
+impl EventSource for CrosstermEvents {
+    fn next(&mut self) -> io::Result<Option<AppEvent>> {
+        loop {
+            if self.terminated.load(Ordering::Relaxed) {
+                return Ok(Some(AppEvent::Terminate));
+            }
+
+            // Steady typing mustn't hold animations back, so ticks are due by the clock.
+            let elapsed = self.last_tick.elapsed();
+            if elapsed >= TICK {
+                self.last_tick = Instant::now();
+                return Ok(Some(AppEvent::Tick));
+            }
+            if !event::poll(TICK - elapsed)? {
+                continue;
+            }
+
+            // Releases, scrolls and keys with no counterpart are skipped.
+            let event = match event::read()? {
+                Event::Key(event) if event.kind == KeyEventKind::Press => key(&event).map(AppEvent::Key),
+                Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Left) =>
+                    Some(AppEvent::Click { column: mouse.column, row: mouse.row }),
+                Event::Resize(width, height) => Some(AppEvent::Resize(width, height)),
+                _ => None
+            };
+
+            if event.is_some() {
+                return Ok(event);
+            }
+        }
+    }
+}
+
+fn key(event: &KeyEvent) -> Option<Key> {
+    let code = match event.code {
+        event::KeyCode::Char(c) => KeyCode::Char(c),
+        event::KeyCode::Enter => KeyCode::Enter,
+        event::KeyCode::Esc => KeyCode::Esc,
+        event::KeyCode::Backspace => KeyCode::Backspace,
+        event::KeyCode::Delete => KeyCode::Delete,
+        event::KeyCode::Tab => KeyCode::Tab,
+        event::KeyCode::Up => KeyCode::Up,
+        event::KeyCode::Down => KeyCode::Down,
+        event::KeyCode::Left => KeyCode::Left,
+        event::KeyCode::Right => KeyCode::Right,
+        event::KeyCode::Home => KeyCode::Home,
+        event::KeyCode::End => KeyCode::End,
+        event::KeyCode::F(n) => KeyCode::F(n),
+        _ => return None
+    };
+
+    let modifiers = Modifiers {
+        ctrl: event.modifiers.contains(KeyModifiers::CONTROL),
+        alt: event.modifiers.contains(KeyModifiers::ALT),
+        shift: event.modifiers.contains(KeyModifiers::SHIFT)
+    };
+
+    Some(Key::new(code, modifiers))
+}
+
+// Plays back a fixed list of events, for driving the game in tests.
+#[cfg(test)]
+pub struct ScriptedEvents {
+    events: std::collections::VecDeque<AppEvent>
+}
+
+#[cfg(test)]
+impl ScriptedEvents {
+    pub fn new(events: impl IntoIterator<Item = AppEvent>) -> Self {
+        Self { events: events.into_iter().collect() }
+    }
+
+    // Each character becomes a plain key press.
+    pub fn typing(text: &str) -> Vec<AppEvent> {
+        text.chars().map(|c| AppEvent::Key(Key::from(KeyCode::Char(c)))).collect()
+    }
+
+    pub fn key(code: KeyCode) -> AppEvent {
+        AppEvent::Key(Key::from(code))
+    }
+}
+
+#[cfg(test)]
+impl EventSource for ScriptedEvents {
+    fn next(&mut self) -> io::Result<Option<AppEvent>> {
+        Ok(self.events.pop_front())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::event_source::{EventSource, ScriptedEvents};
+    use crate::key_bindings::KeyCode;
+
+    #[test]
+    fn should_play_back_events_in_order_then_stop() {
+        let mut events = ScriptedEvents::new(ScriptedEvents::typing("ab"));
+
+        assert_eq!(events.next().unwrap(), Some(ScriptedEvents::key(KeyCode::Char('a'))));
+        assert_eq!(events.next().unwrap(), Some(ScriptedEvents::key(KeyCode::Char('b'))));
+        assert_eq!(events.next().unwrap(), None);
+        assert_eq!(events.next().unwrap(), None);
+    }
+}
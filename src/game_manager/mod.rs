@@ -1,40 +1,871 @@
-use minreq::Error;
+use std::collections::HashSet;
+use crate::action::{Action, Response};
+use crate::animation::{Animation, AnimationKind};
+use crate::config::Config;
+use crate::difficulty::Difficulty;
 use crate::game_manager::GameManagerState::Loading;
-use crate::game_state::GameState;
+use crate::game_state::{GameState, GameStatus, GameUpdateResult};
+use crate::hit_areas::{HitAreas, Target};
+use crate::history::History;
+use crate::key_bindings::KeyBindings;
+use crate::keyboard_layout::KeyboardLayout;
+use crate::language::Language;
+use crate::random::Random;
+use crate::saved_game::SavedGame;
+use crate::guess::CursorMove;
+use crate::language::Messages;
+use crate::screen::{Menu, MenuItem, NewGameState, Screen};
+use crate::settings::{SettingsState, DEFAULT_TRIES, DEFAULT_WORD_LENGTH, TRIES, WORD_LENGTHS};
+use crate::stats::{GameRecord, Stats, Summary};
+use crate::theme::Theme;
+use crate::toast::Toast;
+use crate::word_source::{PickError, WordSource};
+use crate::words::WordPack;
 
 
 pub enum GameManagerState {
     Loading,
     Failure(String),
-    Playing(GameState),
+    Playing(Box<GameState>),
 }
+
+// The game is shown whenever no screen is open on top of it.
 pub struct GameManager {
-    pub state: GameManagerState
+    pub state: GameManagerState,
+    screens: Vec<Screen>,
+    source: WordSource,
+    language: Language,
+    keyboard: Option<KeyboardLayout>,
+    themes: Vec<Theme>,
+    theme: usize,
+    key_bindings: KeyBindings,
+    history: History,
+    stats: Stats,
+    word_len: u8,
+    max_tries: u8,
+    hard_mode: bool,
+    repeat_window: usize,
+    // None picks from the whole word list; games are still counted as normal ones.
+    difficulty: Option<Difficulty>,
+    animations: bool,
+    animation: Option<Animation>,
+    toast: Option<Toast>,
+    bell: bool,
+    accessible: bool,
+    hit_areas: HitAreas,
+    pressed: Option<(Target, u16)>,
+    random: Random,
+    seeded: bool
 }
 
-const WORD_LEN: u8 = 5;
-const MAX_TRIES: u8 = 6;
+pub const DEFAULT_REPEAT_WINDOW: usize = 500;
+// How long the on-screen key stays highlighted after a press, in 50 ms ticks.
+const PRESS_TICKS: u16 = 4;
+const API: &str = "api";
 
 impl GameManager {
-    pub fn new() -> Self {
+    pub fn new(history: History, stats: Stats) -> Self {
         Self {
-            state: Loading
+            state: Loading,
+            screens: Vec::new(),
+            source: WordSource::Api,
+            language: Language::english(),
+            keyboard: None,
+            themes: Theme::built_in(),
+            theme: 0,
+            key_bindings: KeyBindings::default(),
+            history,
+            stats,
+            word_len: DEFAULT_WORD_LENGTH,
+            max_tries: DEFAULT_TRIES,
+            hard_mode: false,
+            repeat_window: DEFAULT_REPEAT_WINDOW,
+            difficulty: None,
+            animations: true,
+            animation: None,
+            toast: None,
+            bell: false,
+            accessible: false,
+            hit_areas: HitAreas::default(),
+            pressed: None,
+            random: Random::new(),
+            seeded: false
+        }
+    }
+
+    // Applies the config, falling back to defaults for invalid values, which are reported back.
+    pub fn configure(&mut self, config: &Config) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        self.word_len = match config.word_length {
+            Some(length) if WORD_LENGTHS.contains(&length) => length,
+            Some(length) => {
+                errors.push(format!("Word length {} isn't between {} and {}", length, WORD_LENGTHS.start(), WORD_LENGTHS.end()));
+                DEFAULT_WORD_LENGTH
+            },
+            None => DEFAULT_WORD_LENGTH
+        };
+
+        self.max_tries = match config.tries {
+            Some(tries) if TRIES.contains(&tries) => tries,
+            Some(tries) => {
+                errors.push(format!("{} tries isn't between {} and {}", tries, TRIES.start(), TRIES.end()));
+                DEFAULT_TRIES
+            },
+            None => DEFAULT_TRIES
+        };
+
+        self.hard_mode = config.hard_mode.unwrap_or(false);
+        self.repeat_window = config.repeat_window.unwrap_or(DEFAULT_REPEAT_WINDOW);
+        self.accessible = config.accessible.unwrap_or(false);
+        // Moving tiles are hard to follow with a screen reader or magnifier.
+        self.animations = config.animations.unwrap_or(true) && !self.accessible;
+        self.bell = config.bell.unwrap_or(false);
+
+        let difficulty = config.difficulty.as_deref().map(str::parse::<Difficulty>).transpose().unwrap_or_else(|error| {
+            errors.push(error);
+            None
+        });
+        self.difficulty = difficulty;
+
+        let language = config.language.as_deref().map(Language::parse).transpose().unwrap_or_else(|error| {
+            errors.push(error);
+            None
+        });
+
+        let pack = match config.words.as_deref() {
+            Some(API) | None => None,
+            Some(reference) => WordPack::resolve(reference).map_err(|error| errors.push(error)).ok()
+        };
+
+        self.language = match (language, &pack) {
+            (Some(language), Some(pack)) => language.with_letters_of(pack),
+            (Some(language), None) => language,
+            (None, Some(pack)) => Language::for_pack(pack),
+            (None, None) => Language::english()
+        };
+
+        // The API only serves English words and difficulty needs a frequency-ranked list, which it doesn't
+        // provide, so a chosen difficulty picks from the language's pack as a new game with one does.
+        let english = self.language.code == Language::english().code;
+        self.source = match (pack, config.words.as_deref()) {
+            (Some(pack), _) => WordSource::Pack(pack),
+            (None, Some(API) | None) if english && difficulty.is_none() => WordSource::Api,
+            (None, words) => {
+                if words == Some(API) && !english {
+                    errors.push(format!("The word API only serves English words, using the '{}' pack instead", self.language.pack));
+                }
+                WordSource::Pack(WordPack::embedded(&self.language.pack).unwrap_or_else(|| WordPack::new(&self.language.pack, Vec::new())))
+            }
+        };
+
+        self.keyboard = config.keyboard.as_deref()
+            .map(|name| KeyboardLayout::find(name, &self.language.alphabet, &config.layouts))
+            .transpose()
+            .unwrap_or_else(|error| {
+                errors.push(error);
+                None
+            });
+
+        self.themes = Theme::all(&config.themes).unwrap_or_else(|error| {
+            errors.push(error);
+            Theme::built_in()
+        });
+        let theme = config.theme.clone().unwrap_or(Theme::dark().name);
+        self.theme = match self.themes.iter().position(|candidate| candidate.name == theme) {
+            Some(index) => index,
+            None => {
+                let names: Vec<&str> = self.themes.iter().map(|theme| theme.name.as_str()).collect();
+                errors.push(format!("Unknown theme '{}', expected one of: {}", theme, names.join(", ")));
+                0
+            }
+        };
+
+        let (key_bindings, key_errors) = KeyBindings::configure(&config.keys);
+        self.key_bindings = key_bindings;
+        errors.extend(key_errors);
+
+        errors
+    }
+
+    pub fn language(&self) -> &Language {
+        &self.language
+    }
+
+    // Keys of the chosen layout, or the language's own layout when none was chosen.
+    pub fn keyboard(&self) -> &[Vec<char>] {
+        match &self.keyboard {
+            Some(keyboard) => &keyboard.rows,
+            None => &self.language.layout
+        }
+    }
+
+    pub fn key_bindings(&self) -> &KeyBindings {
+        &self.key_bindings
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.themes[self.theme]
+    }
+
+    pub fn next_theme(&mut self) {
+        self.theme = (self.theme + 1) % self.themes.len();
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty.unwrap_or_default()
+    }
+
+    pub fn animation(&self) -> Option<&Animation> {
+        self.animation.as_ref()
+    }
+
+    pub fn tick(&mut self) {
+        if self.animation.as_mut().is_some_and(Animation::tick) {
+            self.animation = None;
+        }
+        if self.toast.as_mut().is_some_and(Toast::tick) {
+            self.toast = None;
+        }
+        self.pressed = match self.pressed {
+            Some((target, ticks)) if ticks > 1 => Some((target, ticks - 1)),
+            _ => None
+        };
+    }
+
+    // Highlights the on-screen key for a moment.
+    fn press(&mut self, target: Target) {
+        self.pressed = Some((target, PRESS_TICKS));
+    }
+
+    pub fn pressed(&self) -> Option<Target> {
+        self.pressed.map(|(target, _)| target)
+    }
+
+    pub fn toast(&self) -> Option<&Toast> {
+        self.toast.as_ref()
+    }
+
+    // For front ends without ticks, which show each message once.
+    pub fn take_toast(&mut self) -> Option<Toast> {
+        self.toast.take()
+    }
+
+    pub fn hit_areas(&self) -> &HitAreas {
+        &self.hit_areas
+    }
+
+    pub fn set_hit_areas(&mut self, hit_areas: HitAreas) {
+        self.hit_areas = hit_areas;
+    }
+
+    pub fn bell(&self) -> bool {
+        self.bell
+    }
+
+    pub fn accessible(&self) -> bool {
+        self.accessible
+    }
+
+    // Explains why a guess was rejected.
+    fn notify(&mut self, result: &GameUpdateResult) {
+        if let GameUpdateResult::Rejected(rejection) = result {
+            self.toast = Some(Toast::new(self.language.messages.rejection(rejection)));
         }
     }
 
-    fn fetch_word() -> Result<String, Error> {
-        let response = minreq::get(format!("https://random-word-api.herokuapp.com/word?length={}", WORD_LEN)).send()?;
-        let json = response.json::<Vec<String>>()?;
-        json.first().map(|word| word.to_string()).ok_or(Error::Other("Didn't receive word from the API"))
+    // Committed guesses flip their tiles, rejected ones shake the row and a win bounces it.
+    fn animate_submit(&mut self, result: &GameUpdateResult) {
+        let state = match &self.state {
+            GameManagerState::Playing(state) if self.animations => state,
+            _ => return
+        };
+
+        self.animation = match result {
+            GameUpdateResult::Ok => {
+                let flip = Animation::new(AnimationKind::Flip, state.guess_count() - 1, state.word_len());
+                match state.status {
+                    GameStatus::Won => Some(flip.then(AnimationKind::Bounce)),
+                    _ => Some(flip)
+                }
+            },
+            GameUpdateResult::InvalidInput | GameUpdateResult::Rejected(_) => Some(Animation::new(AnimationKind::Shake, state.guess_count(), state.word_len())),
+            GameUpdateResult::GameFinished => None
+        };
+    }
+
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    pub fn summary(&self) -> Summary {
+        self.stats.by_difficulty().remove(&self.difficulty()).unwrap_or_default()
+    }
+
+    fn record_result(&mut self) {
+        if let GameManagerState::Playing(state) = &self.state {
+            if state.status != GameStatus::Pending {
+                let record = GameRecord {
+                    difficulty: self.difficulty(),
+                    won: state.status == GameStatus::Won,
+                    guesses: state.guess_count(),
+                    tries: Some(state.max_tries),
+                    answer: state.target_word.clone(),
+                    words: state.guessed_words()
+                };
+                let _ = self.stats.record(record);
+                // Only finished games count towards the repeat window; an abandoned answer can come up again.
+                // Losing the history only risks a repeated answer, so it shouldn't stop the game.
+                // Seeded games replay the same answers, so they'd only crowd out the real ones.
+                if !self.seeded {
+                    let _ = self.history.record(&state.target_word);
+                }
+            }
+        }
+    }
+
+    pub fn screen(&mut self) -> Option<&mut Screen> {
+        self.screens.last_mut()
+    }
+
+    pub fn push_screen(&mut self, screen: Screen) {
+        self.screens.push(screen);
+    }
+
+    pub fn pop_screen(&mut self) {
+        self.screens.pop();
+    }
+
+    pub fn can_continue(&self) -> bool {
+        matches!(&self.state, GameManagerState::Playing(state) if state.status == GameStatus::Pending)
+    }
+
+    pub fn open_menu(&mut self) {
+        let menu = Menu::new(self.can_continue());
+        self.push_screen(Screen::MainMenu(menu));
+    }
+
+    pub fn open_new_game(&mut self) {
+        let new_game = NewGameState::new(self.difficulty(), self.hard_mode, self.word_len);
+        self.push_screen(Screen::NewGame(new_game));
+    }
+
+    // Settings are edited as saved in the config file, without command line overrides.
+    pub fn open_settings(&mut self) {
+        let settings = match Config::load() {
+            Ok(config) => SettingsState::new(config, None),
+            Err(error) => SettingsState::new(Config::default(), Some(error))
+        };
+        self.push_screen(Screen::Settings(Box::new(settings)));
+    }
+
+    // Cosmetic changes apply to the game in progress, the others start a new game.
+    pub fn save_settings(&mut self) {
+        let (config, changes_game) = match self.screens.last_mut() {
+            Some(Screen::Settings(settings)) => match settings.config.save() {
+                Ok(()) => (settings.config.clone(), settings.changes_game()),
+                Err(error) => {
+                    settings.error = Some(error);
+                    return;
+                }
+            },
+            _ => return
+        };
+
+        let errors = self.configure(&config);
+        self.screens.clear();
+        if changes_game || !matches!(self.state, GameManagerState::Playing(_)) {
+            self.restart();
+        }
+        if !errors.is_empty() {
+            self.open_menu();
+            self.push_screen(Screen::InvalidConfig(errors));
+        }
+    }
+
+    // The picked mode only lasts for this session, the config file is left untouched.
+    pub fn start_game(&mut self, new_game: &NewGameState) {
+        if new_game.difficulty != self.difficulty() {
+            if let WordSource::Api = self.source {
+                self.source = WordSource::Pack(WordPack::embedded(&self.language.pack).unwrap_or_else(|| WordPack::new(&self.language.pack, Vec::new())));
+            }
+            self.difficulty = Some(new_game.difficulty);
+        }
+        self.hard_mode = new_game.hard_mode;
+        self.word_len = new_game.word_length;
+        self.screens.clear();
+        self.restart();
+    }
+
+    // Replaces the pending guess with a whole word and submits it, for front ends
+    // reading words rather than keys.
+    pub fn enter_word(&mut self, word: &str) -> Response {
+        if let GameManagerState::Playing(state) = &self.state {
+            for _ in 0..state.word_len() {
+                self.perform(Action::Delete);
+            }
+        }
+        for letter in word.chars() {
+            self.perform(Action::Type(letter));
+        }
+        self.perform(Action::Submit)
+    }
+
+    // Whether letters currently type into a guess rather than trigger their bindings.
+    pub fn typing(&self) -> bool {
+        self.screens.is_empty() && self.can_continue()
+    }
+
+    pub fn perform(&mut self, action: Action) -> Response {
+        if action == Action::Theme {
+            self.next_theme();
+            return Response::Continue;
+        }
+
+        if !self.screens.is_empty() {
+            return self.perform_on_screen(action);
+        }
+
+        match action {
+            Action::Quit => self.open_menu(),
+            Action::Settings => self.open_settings(),
+            Action::ShowStats => self.push_screen(Screen::Statistics),
+            Action::Help => self.push_screen(Screen::Help),
+            Action::Restart => self.restart(),
+            _ if self.can_continue() => return self.play(action),
+            Action::Submit if !matches!(self.state, GameManagerState::Loading) => self.restart(),
+            _ => {}
+        }
+        Response::Continue
+    }
+
+    fn play(&mut self, action: Action) -> Response {
+        let state = match &mut self.state {
+            GameManagerState::Playing(state) => state,
+            _ => return Response::Continue
+        };
+
+        let (result, pressed) = match action {
+            Action::Type(letter) => (state.push_letter(letter), Some(Target::Key(letter))),
+            Action::Delete => (state.pop_letter(), Some(Target::Backspace)),
+            Action::Submit => (state.commit_guess(), Some(Target::Enter)),
+            Action::Clear => (state.delete_letter(), None),
+            Action::Left => (state.move_cursor(CursorMove::Left), None),
+            Action::Right => (state.move_cursor(CursorMove::Right), None),
+            Action::Home => (state.move_cursor(CursorMove::Home), None),
+            Action::End => (state.move_cursor(CursorMove::End), None),
+            Action::Hint => {
+                if let Some((position, letter)) = state.hint() {
+                    let hint = Messages::fill(self.language.messages.hint, &[("position", &position), ("letter", &letter.to_uppercase())]);
+                    self.toast = Some(Toast::new(hint));
+                }
+                (GameUpdateResult::Ok, None)
+            },
+            _ => (GameUpdateResult::Ok, None)
+        };
+
+        let finished = state.status != GameStatus::Pending;
+
+        self.notify(&result);
+        if let Some(target) = pressed {
+            self.press(target);
+        }
+        if action == Action::Submit {
+            self.animate_submit(&result);
+        }
+        if finished {
+            self.record_result();
+        }
+
+        match result {
+            GameUpdateResult::InvalidInput | GameUpdateResult::Rejected(_) => Response::Rejected,
+            _ => Response::Continue
+        }
+    }
+
+    // Every screen takes the same actions: up and down to choose, left and right to change,
+    // submit to confirm and quit to go back. Going back from the main menu without a game to continue quits.
+    fn perform_on_screen(&mut self, action: Action) -> Response {
+        let can_continue = self.can_continue();
+
+        match self.screens.last_mut() {
+            Some(Screen::MainMenu(menu)) => match action {
+                Action::Up => menu.select_previous(),
+                Action::Down => menu.select_next(),
+                Action::Choose(i) => {
+                    menu.selected = i;
+                    return self.perform_on_screen(Action::Submit);
+                },
+                Action::Submit => match menu.selected() {
+                    MenuItem::Continue => self.pop_screen(),
+                    MenuItem::NewGame => self.open_new_game(),
+                    MenuItem::Statistics => self.push_screen(Screen::Statistics),
+                    MenuItem::Settings => self.open_settings(),
+                    MenuItem::Help => self.push_screen(Screen::Help),
+                    MenuItem::About => self.push_screen(Screen::About),
+                    MenuItem::Quit => return Response::Quit
+                },
+                Action::Quit if !can_continue => return Response::Quit,
+                Action::Quit => self.pop_screen(),
+                _ => {}
+            },
+            Some(Screen::NewGame(new_game)) => match action {
+                Action::Up => new_game.select_previous(),
+                Action::Down => new_game.select_next(),
+                Action::Left => new_game.change(false),
+                Action::Right => new_game.change(true),
+                Action::Choose(i) => {
+                    new_game.selected = i;
+                    new_game.change(true);
+                },
+                Action::Submit => {
+                    let new_game = new_game.clone();
+                    self.start_game(&new_game)
+                },
+                Action::Quit => self.pop_screen(),
+                _ => {}
+            },
+            Some(Screen::Settings(settings)) => match action {
+                Action::Up => settings.select_previous(),
+                Action::Down => settings.select_next(),
+                Action::Left => settings.change(false),
+                Action::Right => settings.change(true),
+                Action::Choose(i) => {
+                    settings.selected = i;
+                    settings.change(true);
+                },
+                Action::Submit => self.save_settings(),
+                Action::Quit => self.pop_screen(),
+                _ => {}
+            },
+            Some(Screen::InvalidConfig(_)) => match action {
+                Action::Settings => {
+                    self.pop_screen();
+                    self.open_settings();
+                },
+                Action::Submit | Action::Quit => self.pop_screen(),
+                _ => {}
+            },
+            Some(Screen::Statistics | Screen::Help | Screen::About) => {
+                if matches!(action, Action::Submit | Action::Quit) {
+                    self.pop_screen();
+                }
+            },
+            None => {}
+        }
+
+        Response::Continue
+    }
+
+    fn pick_word(&mut self) -> Result<String, PickError> {
+        // A seeded run has to pick the same words whatever was played before it.
+        let excluded = if self.seeded { HashSet::new() } else { self.history.recent(self.repeat_window) };
+        let history = &self.history;
+        self.source.pick(self.word_len, self.difficulty, &mut self.random, &excluded, |word| history.games_since(word))
+    }
+
+    fn new_state(&self, word: &str, max_tries: u8) -> GameState {
+        let mut state = GameState::new(word, max_tries, &self.language.alphabet);
+        if let WordSource::Pack(pack) = &self.source {
+            state.set_dictionary(pack.with_length(word.chars().count()).words());
+        }
+        state
+    }
+
+    // The game in progress, if there's one worth coming back to. A seeded one can be replayed instead.
+    pub fn saved_game(&self) -> Option<SavedGame> {
+        match &self.state {
+            GameManagerState::Playing(state) if state.status == GameStatus::Pending && !self.seeded => Some(SavedGame {
+                difficulty: self.difficulty(),
+                hard_mode: state.hard_mode(),
+                max_tries: state.max_tries,
+                target_word: state.target_word.clone(),
+                guesses: state.guessed_words()
+            }),
+            _ => None
+        }
+    }
+
+    // Replays the saved guesses. A game that no longer fits the alphabet or
+    // word list, e.g. after switching languages, is dropped.
+    pub fn resume(&mut self, game: SavedGame) -> bool {
+        let mut state = self.new_state(&game.target_word, game.max_tries);
+        state.set_hard_mode(game.hard_mode);
+
+        for guess in &game.guesses {
+            if guess.chars().any(|letter| state.push_letter(letter) != GameUpdateResult::Ok)
+                || state.commit_guess() != GameUpdateResult::Ok {
+                return false;
+            }
+        }
+
+        if state.status != GameStatus::Pending {
+            return false;
+        }
+
+        if game.difficulty != self.difficulty() {
+            self.difficulty = Some(game.difficulty);
+        }
+        self.state = GameManagerState::Playing(Box::new(state));
+        true
+    }
+
+    // Makes the picked words repeatable. The API picks its words itself, so it can't be seeded.
+    pub fn seed(&mut self, seed: u64) -> Result<(), String> {
+        if let WordSource::Api = self.source {
+            return Err("--seed needs a word pack, pick one with --words".to_string());
+        }
+        self.random = Random::with_seed(seed);
+        self.seeded = true;
+        Ok(())
+    }
+
+    pub fn seeded(&self) -> bool {
+        self.seeded
     }
 
     pub fn restart(&mut self) {
+        self.animation = None;
+        self.toast = None;
 
-        match GameManager::fetch_word() {
-            Ok(word) => self.state = GameManagerState::Playing(GameState::new(&word, MAX_TRIES)),
-            Err(_) => self.state = GameManagerState::Failure("Failed to fetch your word".to_string())
+        match self.pick_word() {
+            Ok(word) => {
+                let mut state = self.new_state(&word, self.max_tries);
+                state.set_hard_mode(self.hard_mode);
+                self.state = GameManagerState::Playing(Box::new(state))
+            },
+            Err(error) => self.state = GameManagerState::Failure(self.language.messages.pick_error(&error))
         }
 
     }
 
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::{Action, Response};
+    use crate::animation::{Animation, AnimationKind};
+    use crate::config::Config;
+    use crate::game_manager::{GameManager, GameManagerState};
+    use crate::difficulty::Difficulty;
+    use crate::game_state::GameUpdateResult;
+    use crate::hit_areas::Target;
+    use crate::history::History;
+    use crate::saved_game::SavedGame;
+    use crate::screen::{NewGameState, Screen};
+    use crate::stats::Stats;
+    use crate::word_source::WordSource;
+
+    fn manager() -> GameManager {
+        GameManager::new(History::in_memory(), Stats::in_memory())
+    }
+
+    #[test]
+    fn should_report_invalid_settings_and_fall_back_to_defaults() {
+        let config = Config {
+            word_length: Some(42),
+            theme: Some("neon".to_string()),
+            language: Some("pl".to_string()),
+            ..Config::default()
+        };
+        let mut manager = manager();
+        let errors = manager.configure(&config);
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(manager.theme().name, "dark");
+        assert_eq!(manager.language().code, "pl");
+    }
+
+    #[test]
+    fn should_start_games_with_configured_length_and_tries() {
+        let config = Config {
+            word_length: Some(4),
+            tries: Some(8),
+            words: Some("en".to_string()),
+            ..Config::default()
+        };
+        let mut manager = manager();
+        assert!(manager.configure(&config).is_empty());
+        manager.restart();
+
+        match &manager.state {
+            GameManagerState::Playing(state) => {
+                assert_eq!(state.word_len(), 4);
+                assert_eq!(state.max_tries, 8);
+            },
+            _ => panic!("Expected a game to start")
+        }
+    }
+
+    #[test]
+    fn should_pick_from_the_pack_when_the_api_is_asked_for_a_difficulty() {
+        let mut manager = manager();
+        let errors = manager.configure(&Config { words: Some("api".to_string()), difficulty: Some("easy".to_string()), ..Config::default() });
+
+        assert!(errors.is_empty());
+        assert!(matches!(&manager.source, WordSource::Pack(pack) if pack.name() == "en"));
+        assert_eq!(manager.difficulty, Some(Difficulty::Easy));
+    }
+
+    #[test]
+    fn should_start_picked_mode_and_close_screens() {
+        let mut manager = manager();
+        manager.configure(&Config { words: Some("en".to_string()), ..Config::default() });
+        manager.open_menu();
+        manager.open_new_game();
+        manager.start_game(&NewGameState::new(Difficulty::Easy, true, 4));
+
+        assert!(manager.screen().is_none());
+        assert_eq!(manager.difficulty(), Difficulty::Easy);
+        assert!(manager.can_continue());
+    }
+
+    #[test]
+    fn should_only_animate_when_enabled() {
+        let mut manager = manager();
+        manager.configure(&Config { words: Some("en".to_string()), animations: Some(false), ..Config::default() });
+        manager.restart();
+        manager.animate_submit(&GameUpdateResult::InvalidInput);
+        assert!(manager.animation().is_none());
+
+        manager.configure(&Config { words: Some("en".to_string()), ..Config::default() });
+        manager.animate_submit(&GameUpdateResult::InvalidInput);
+        assert_eq!(manager.animation().map(Animation::kind), Some(AnimationKind::Shake));
+
+        manager.animation = None;
+        manager.configure(&Config { words: Some("en".to_string()), accessible: Some(true), ..Config::default() });
+        manager.animate_submit(&GameUpdateResult::InvalidInput);
+        assert!(manager.animation().is_none());
+    }
+
+    #[test]
+    fn should_release_pressed_key_after_a_moment() {
+        let mut manager = manager();
+        manager.press(Target::Key('a'));
+        for _ in 0..3 {
+            manager.tick();
+        }
+        assert_eq!(manager.pressed(), Some(Target::Key('a')));
+        manager.tick();
+        assert_eq!(manager.pressed(), None);
+    }
+
+    #[test]
+    fn should_go_back_to_previous_screen() {
+        let mut manager = manager();
+        manager.open_menu();
+        manager.push_screen(Screen::Help);
+        manager.pop_screen();
+
+        assert!(matches!(manager.screen(), Some(Screen::MainMenu(_))));
+        assert!(!manager.can_continue());
+    }
+
+    #[test]
+    fn should_quit_from_main_menu_only_without_game_to_continue() {
+        let mut manager = manager();
+        manager.configure(&Config { words: Some("en".to_string()), ..Config::default() });
+        manager.open_menu();
+        assert_eq!(manager.perform(Action::Quit), Response::Quit);
+
+        manager.perform(Action::Submit);
+        manager.perform(Action::Submit);
+        assert!(manager.typing());
+        manager.perform(Action::Quit);
+        assert_eq!(manager.perform(Action::Quit), Response::Continue);
+        assert!(manager.typing());
+    }
+
+    #[test]
+    fn should_reject_submitting_incomplete_guess() {
+        let mut manager = manager();
+        manager.configure(&Config { words: Some("en".to_string()), ..Config::default() });
+        manager.restart();
+        manager.perform(Action::Type('a'));
+        assert_eq!(manager.pressed(), Some(Target::Key('a')));
+        assert_eq!(manager.perform(Action::Submit), Response::Rejected);
+        assert_eq!(manager.toast().map(|toast| toast.message.as_str()), Some("Not enough letters"));
+    }
+
+    #[test]
+    fn should_only_remember_answers_of_finished_games() {
+        let mut manager = manager();
+        manager.configure(&Config { words: Some("en".to_string()), ..Config::default() });
+        manager.restart();
+        manager.restart();
+        assert!(manager.history.recent(10).is_empty());
+
+        let GameManagerState::Playing(state) = &manager.state else {
+            panic!("Expected a game");
+        };
+        let answer = state.target_word.clone();
+        assert_eq!(manager.enter_word(&answer), Response::Continue);
+        assert_eq!(manager.history.games_since(&answer), Some(0));
+    }
+
+    #[test]
+    fn should_pick_the_same_words_with_a_seed_whatever_the_history() {
+        let config = Config { words: Some("en".to_string()), ..Config::default() };
+        let mut first = manager();
+        first.configure(&config);
+        assert_eq!(first.seed(42), Ok(()));
+        first.restart();
+        let GameManagerState::Playing(state) = &first.state else {
+            panic!("Expected a game");
+        };
+        let answer = state.target_word.clone();
+        assert_eq!(first.enter_word(&answer), Response::Continue);
+        assert!(first.history.recent(10).is_empty());
+
+        let mut history = History::in_memory();
+        history.record(&answer).unwrap();
+        let mut second = GameManager::new(history, Stats::in_memory());
+        second.configure(&config);
+        assert_eq!(second.seed(42), Ok(()));
+        second.restart();
+        assert!(matches!(&second.state, GameManagerState::Playing(state) if state.target_word == answer));
+    }
+
+    #[test]
+    fn should_not_seed_the_word_api() {
+        let mut manager = manager();
+        manager.configure(&Config::default());
+        assert!(manager.seed(42).is_err());
+    }
+
+    #[test]
+    fn should_resume_saved_game_and_save_it_again() {
+        let mut manager = manager();
+        manager.configure(&Config { words: Some("en".to_string()), ..Config::default() });
+        let game = SavedGame {
+            difficulty: Difficulty::Hard,
+            hard_mode: true,
+            max_tries: 6,
+            target_word: "crane".to_string(),
+            guesses: vec!["trace".to_string()]
+        };
+
+        assert!(manager.resume(game.clone()));
+        assert!(manager.can_continue());
+        assert_eq!(manager.difficulty(), Difficulty::Hard);
+        assert_eq!(manager.saved_game(), Some(game));
+    }
+
+    #[test]
+    fn should_drop_saved_game_that_no_longer_fits() {
+        let mut manager = manager();
+        manager.configure(&Config { words: Some("en".to_string()), ..Config::default() });
+        let game = SavedGame {
+            difficulty: Difficulty::Normal,
+            hard_mode: false,
+            max_tries: 6,
+            target_word: "crane".to_string(),
+            guesses: vec!["żółty".to_string()]
+        };
+        assert!(!manager.resume(game.clone()));
+
+        let finished = SavedGame { guesses: vec!["crane".to_string()], ..game };
+        assert!(!manager.resume(finished));
+        assert_eq!(manager.saved_game(), None);
+    }
+}
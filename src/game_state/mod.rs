@@ -1,5 +1,6 @@
-use crate::guess::{CompletedGuess, EmptyGuess, Guess, PendingGuess};
-use crate::letters::Letters;
+use std::collections::HashSet;
+use crate::guess::{word_len, CompletedGuess, CursorMove, EmptyGuess, Guess, PendingGuess};
+use crate::letters::{LetterState, Letters};
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum GameStatus {
     Pending,
@@ -7,11 +8,21 @@ pub enum GameStatus {
     Lost
 }
 
+// Why a guess couldn't be submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    NotEnoughLetters,
+    NotInWordList,
+    MustContain(char),
+    MustBeAt(char, usize)
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum GameUpdateResult {
     Ok,
     GameFinished,
-    InvalidInput
+    InvalidInput,
+    Rejected(Rejection)
 }
 
 #[derive(Clone)]
@@ -21,7 +32,9 @@ pub struct GameState {
     pending_guess: Option<PendingGuess>,
     pub(crate) letters: Letters,
     pub(crate) max_tries: u8,
-    pub(crate) status: GameStatus
+    pub(crate) status: GameStatus,
+    hard_mode: bool,
+    dictionary: Option<HashSet<String>>
 }
 
 impl GameState {
@@ -38,17 +51,76 @@ impl GameState {
         }
     }
 
-    pub(crate) fn new(target_word: &str, max_tries: u8) -> Self {
+    pub(crate) fn new(target_word: &str, max_tries: u8, alphabet: &[char]) -> Self {
         Self{
             target_word: target_word.to_string(),
             guesses: Vec::new(),
             pending_guess: Some(PendingGuess::new(target_word.to_string())),
-            letters: Letters::new(),
+            letters: Letters::new(alphabet),
             max_tries,
-            status: GameStatus::Pending
+            status: GameStatus::Pending,
+            hard_mode: false,
+            dictionary: None
         }
     }
 
+    pub(crate) fn set_hard_mode(&mut self, hard_mode: bool) {
+        self.hard_mode = hard_mode;
+    }
+
+    // Without a dictionary any word is accepted. The answer is always accepted.
+    pub(crate) fn set_dictionary(&mut self, words: &[String]) {
+        let mut dictionary: HashSet<String> = words.iter().cloned().collect();
+        dictionary.insert(self.target_word.clone());
+        self.dictionary = Some(dictionary);
+    }
+
+    // In hard mode green letters have to stay in place and yellow ones have to be reused.
+    fn hint_violation(&self, guess: &PendingGuess) -> Option<Rejection> {
+        let letters = guess.letters();
+
+        self.guesses.iter().flat_map(|previous| previous.letters().iter().enumerate()).find_map(|(i, hint)| match hint.state {
+            LetterState::RightPosition if letters.get(i).map(|l| l.value) != Some(hint.value) => Some(Rejection::MustBeAt(hint.value, i + 1)),
+            LetterState::WrongPosition if !letters.iter().any(|l| l.value == hint.value) => Some(Rejection::MustContain(hint.value)),
+            _ => None
+        })
+    }
+
+    fn rejection(&self, guess: &PendingGuess) -> Option<Rejection> {
+        if !guess.full() {
+            return Some(Rejection::NotEnoughLetters);
+        }
+        if self.dictionary.as_ref().is_some_and(|dictionary| !dictionary.contains(&guess.word())) {
+            return Some(Rejection::NotInWordList);
+        }
+        if self.hard_mode {
+            return self.hint_violation(guess);
+        }
+        None
+    }
+
+    pub(crate) fn word_len(&self) -> usize {
+        word_len(&self.target_word)
+    }
+
+    pub(crate) fn guess_count(&self) -> usize {
+        self.guesses.len()
+    }
+
+    pub(crate) fn hard_mode(&self) -> bool {
+        self.hard_mode
+    }
+
+    // Committed guesses as typed, oldest first.
+    pub(crate) fn guessed_words(&self) -> Vec<String> {
+        self.guesses.iter().map(|guess| guess.letters().iter().map(|letter| letter.value).collect()).collect()
+    }
+
+    // Whether submitting the current guess would be accepted.
+    pub(crate) fn can_commit(&self) -> bool {
+        self.current_guess().is_some_and(|guess| self.rejection(guess).is_none())
+    }
+
     fn current_guess(&self) -> Option<&PendingGuess> {
         self.pending_guess.as_ref()
     }
@@ -58,11 +130,11 @@ impl GameState {
     }
 
     pub(crate) fn push_letter(&mut self, letter: char) -> GameUpdateResult {
-        let is_alpha= letter.is_alphabetic();
+        let in_alphabet = self.letters.contains(letter);
 
         match self.current_guess_mut() {
-            Some(guess) if is_alpha => guess.push_letter(letter),
-            _ => return GameUpdateResult::InvalidInput
+            Some(guess) if in_alphabet => guess.push_letter(letter),
+            _ => GameUpdateResult::InvalidInput
         }
     }
 
@@ -73,16 +145,47 @@ impl GameState {
         }
     }
 
+    pub(crate) fn delete_letter(&mut self) -> GameUpdateResult {
+        match self.current_guess_mut() {
+            Some(guess) => guess.delete_letter(),
+            _ => GameUpdateResult::InvalidInput
+        }
+    }
+
+    pub(crate) fn move_cursor(&mut self, direction: CursorMove) -> GameUpdateResult {
+        match self.current_guess_mut() {
+            Some(guess) => guess.move_cursor(direction),
+            _ => GameUpdateResult::InvalidInput
+        }
+    }
+
+    // The first letter of the answer not yet guessed in its place, with its position counted from 1.
+    pub(crate) fn hint(&self) -> Option<(usize, char)> {
+        self.target_word.chars().enumerate()
+            .find(|(i, c)| !self.guesses.iter().any(|guess| guess.letters().get(*i).is_some_and(|l| l.value == *c)))
+            .map(|(i, c)| (i + 1, c))
+    }
+
+    // Position of the cursor within the row being typed.
+    pub(crate) fn cursor(&self) -> Option<usize> {
+        self.current_guess().map(PendingGuess::cursor)
+    }
+
     pub(crate) fn commit_guess(&mut self) -> GameUpdateResult {
-        match self.current_guess().cloned() {
-            Some(guess) if guess.full() => {
+        let guess = match self.current_guess().cloned() {
+            Some(guess) => guess,
+            None => return GameUpdateResult::GameFinished
+        };
+
+        match self.rejection(&guess) {
+            None => {
                 if self.guesses.len() >= self.max_tries as usize {
                     self.pending_guess = None;
                 }
                 let committed_guess = guess.complete();
 
                 let all_correct = committed_guess.is_all_correct();
-                self.letters.update_many(&committed_guess.letters());
+                self.letters.update_many(committed_guess.letters());
                 self.guesses.push(committed_guess);
 
                 if all_correct {
@@ -97,8 +200,7 @@ impl GameState {
 
                 GameUpdateResult::Ok
             }
-            Some(_) => GameUpdateResult::InvalidInput,
-            None => GameUpdateResult::GameFinished
+            Some(rejection) => GameUpdateResult::Rejected(rejection)
         }
     }
 
@@ -106,12 +208,13 @@ impl GameState {
 
 #[cfg(test)]
 mod tests {
-    use crate::game_state::{GameState, GameUpdateResult};
+    use crate::game_state::{GameState, GameUpdateResult, Rejection};
     use crate::guess::Guess;
+    use crate::language::Language;
     use crate::letters::{GuessedLetter, LetterState};
 
     fn setup() -> GameState {
-        GameState::new("test", 3)
+        GameState::new("test", 3, &Language::english().alphabet)
     }
 
     #[test]
@@ -121,7 +224,7 @@ mod tests {
         state.push_letter('e');
         state.push_letter('s');
         let result = state.commit_guess();
-        assert_eq!(result, GameUpdateResult::InvalidInput);
+        assert_eq!(result, GameUpdateResult::Rejected(Rejection::NotEnoughLetters));
     }
 
     #[test]
@@ -193,12 +296,11 @@ mod tests {
         state.push_letter('s');
         state.push_letter('x');
         state.commit_guess();
-        let mut state = setup();
         state.push_letter('t');
         state.push_letter('e');
         state.push_letter('x');
         state.push_letter('t');
-        let mut state = setup();
+        state.commit_guess();
         state.push_letter('x');
         state.push_letter('e');
         state.push_letter('x');
@@ -208,4 +310,82 @@ mod tests {
         assert_eq!(state.status, crate::game_state::GameStatus::Lost);
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn should_only_accept_letters_of_the_alphabet() {
+        let mut state = setup();
+        assert_eq!(state.push_letter('ł'), GameUpdateResult::InvalidInput);
+        assert_eq!(state.push_letter('1'), GameUpdateResult::InvalidInput);
+        assert_eq!(state.push_letter('t'), GameUpdateResult::Ok);
+    }
+
+    #[test]
+    fn should_count_letters_of_non_english_words() {
+        let mut state = GameState::new("łódź", 6, &Language::by_code("pl").unwrap().alphabet);
+        for letter in ['ł', 'ó', 'd', 'ź'] {
+            assert_eq!(state.push_letter(letter), GameUpdateResult::Ok);
+        }
+        assert_eq!(state.push_letter('a'), GameUpdateResult::InvalidInput);
+        assert_eq!(state.commit_guess(), GameUpdateResult::Ok);
+        assert_eq!(state.status, crate::game_state::GameStatus::Won);
+    }
+
+    #[test]
+    fn should_require_revealed_hints_in_hard_mode() {
+        let mut state = setup();
+        state.set_hard_mode(true);
+        for letter in ['t', 's', 'x', 'x'] {
+            state.push_letter(letter);
+        }
+        state.commit_guess();
+
+        for letter in ['x', 'x', 'x', 'x'] {
+            state.push_letter(letter);
+        }
+        assert_eq!(state.commit_guess(), GameUpdateResult::Rejected(Rejection::MustBeAt('t', 1)));
+
+        state.pop_letter();
+        state.pop_letter();
+        state.pop_letter();
+        state.pop_letter();
+        for letter in ['t', 'x', 'x', 'x'] {
+            state.push_letter(letter);
+        }
+        assert_eq!(state.commit_guess(), GameUpdateResult::Rejected(Rejection::MustContain('s')));
+
+        for _ in 0..3 {
+            state.pop_letter();
+        }
+        for letter in ['e', 's', 't'] {
+            state.push_letter(letter);
+        }
+        assert_eq!(state.commit_guess(), GameUpdateResult::Ok);
+    }
+
+    #[test]
+    fn should_hint_first_letter_not_yet_found() {
+        let mut state = setup();
+        assert_eq!(state.hint(), Some((1, 't')));
+        for letter in ['t', 'e', 'x', 't'] {
+            state.push_letter(letter);
+        }
+        state.commit_guess();
+        assert_eq!(state.hint(), Some((3, 's')));
+    }
+
+    #[test]
+    fn should_only_accept_words_from_dictionary() {
+        let mut state = setup();
+        state.set_dictionary(&["text".to_string()]);
+        for letter in ['t', 'e', 'x', 's'] {
+            state.push_letter(letter);
+        }
+        assert!(!state.can_commit());
+        assert_eq!(state.commit_guess(), GameUpdateResult::Rejected(Rejection::NotInWordList));
+
+        state.pop_letter();
+        state.push_letter('t');
+        assert!(state.can_commit());
+        assert_eq!(state.commit_guess(), GameUpdateResult::Ok);
+    }
+
+}
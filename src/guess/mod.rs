@@ -1,8 +1,28 @@
+use unicode_segmentation::UnicodeSegmentation;
 use crate::game_state::GameUpdateResult;
 use crate::letters::{GuessedLetter, LetterState};
-pub trait Guess {
 
-    fn is_pending(&self) -> bool;
+// Value of cells nothing was typed into yet.
+const BLANK: char = ' ';
+
+pub(crate) fn word_len(word: &str) -> usize {
+    word.graphemes(true).count()
+}
+// How a guess is coloured. A letter found anywhere else in the answer counts as
+// present, however many times it's repeated.
+pub(crate) fn score(guess: &str, target_word: &str) -> Vec<LetterState> {
+    guess.chars().zip(target_word.chars()).map(|(letter, expected)| {
+        if letter == expected {
+            LetterState::RightPosition
+        } else if target_word.contains(letter) {
+            LetterState::WrongPosition
+        } else {
+            LetterState::NotOccurring
+        }
+    }).collect()
+}
+
+pub trait Guess {
     fn letters(&self) -> &Vec<GuessedLetter>;
 }
 
@@ -12,11 +32,6 @@ static EMPTY: Vec<GuessedLetter> = Vec::new();
 
 impl Guess for EmptyGuess {
 
-
-    fn is_pending(&self) -> bool {
-        false
-    }
-
     fn letters(&self) -> &Vec<GuessedLetter> {
         &EMPTY
     }
@@ -28,10 +43,6 @@ pub struct CompletedGuess {
 }
 
 impl Guess for CompletedGuess {
-    fn is_pending(&self) -> bool {
-        false
-    }
-
     fn letters(&self) -> &Vec<GuessedLetter> {
         &self.letters
     }
@@ -43,9 +54,21 @@ impl CompletedGuess {
     }
 }
 
+// Moves of the cursor within the pending guess.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CursorMove {
+    Left,
+    Right,
+    Home,
+    End
+}
+
+// A row being typed: one cell per letter of the answer, blank until filled, with a cursor
+// that sits on the cell the next letter goes to, or past the last one.
 #[derive(Clone)]
 pub struct PendingGuess {
     letters: Vec<GuessedLetter>,
+    cursor: usize,
     target_word: String
 }
 
@@ -53,55 +76,132 @@ impl PendingGuess {
 
     pub(crate) fn new(target_word: String) -> Self {
         Self{
-            letters: Vec::new(),
+            letters: vec![GuessedLetter::default(); word_len(&target_word)],
+            cursor: 0,
             target_word
         }
     }
 
     pub(crate) fn complete(&self) -> CompletedGuess {
 
-        let letters = self.letters.iter().zip(self.target_word.chars()).map(|(l, c)| {
-            if l.value == c {
-                GuessedLetter::new(l.value, LetterState::RightPosition)
-            } else if self.target_word.contains(l.value) {
-                GuessedLetter::new(l.value, LetterState::WrongPosition)
-            } else {
-                GuessedLetter::new(l.value, LetterState::NotOccurring)
-            }
-        }).collect();
+        let word: String = self.letters.iter().map(|l| l.value).collect();
+        let letters = self.letters.iter().zip(score(&word, &self.target_word))
+            .map(|(l, state)| GuessedLetter::new(l.value, state))
+            .collect();
 
         CompletedGuess { letters }
     }
 
+    fn is_blank(&self, i: usize) -> bool {
+        self.letters.get(i).is_none_or(|letter| letter.value == BLANK)
+    }
+
     pub(crate) fn full(&self) -> bool {
-        self.letters.len() == self.target_word.len()
+        (0..self.letters.len()).all(|i| !self.is_blank(i))
     }
 
+    pub(crate) fn word(&self) -> String {
+        self.letters.iter().map(|letter| letter.value).collect()
+    }
+
+    pub(crate) fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    // Types over the cell under the cursor and moves on to the next one.
     pub(crate) fn push_letter(&mut self, letter: char) -> GameUpdateResult {
-        if self.full() {
-            GameUpdateResult::InvalidInput
-        } else {
-            self.letters.push(GuessedLetter::new(letter, LetterState::default()));
-            GameUpdateResult::Ok
+        match self.letters.get_mut(self.cursor) {
+            Some(cell) => {
+                *cell = GuessedLetter::new(letter, LetterState::default());
+                self.cursor += 1;
+                GameUpdateResult::Ok
+            },
+            None => GameUpdateResult::InvalidInput
         }
     }
 
+    // Clears the cell before the cursor and moves onto it.
     pub(crate) fn pop_letter(&mut self) -> GameUpdateResult {
-        if self.letters.len() > 0 {
-            self.letters.pop();
+        if self.cursor > 0 && self.letters.iter().any(|letter| letter.value != BLANK) {
+            self.cursor -= 1;
+            self.letters[self.cursor] = GuessedLetter::default();
             GameUpdateResult::Ok
         } else {
             GameUpdateResult::InvalidInput
         }
     }
-}
 
-impl Guess for PendingGuess {
-    fn is_pending(&self) -> bool {
-        true
+    // Clears the cell under the cursor.
+    pub(crate) fn delete_letter(&mut self) -> GameUpdateResult {
+        if self.is_blank(self.cursor) {
+            GameUpdateResult::InvalidInput
+        } else {
+            self.letters[self.cursor] = GuessedLetter::default();
+            GameUpdateResult::Ok
+        }
+    }
+
+    pub(crate) fn move_cursor(&mut self, direction: CursorMove) -> GameUpdateResult {
+        let cursor = match direction {
+            CursorMove::Left if self.cursor > 0 => self.cursor - 1,
+            CursorMove::Right if self.cursor < self.letters.len() => self.cursor + 1,
+            CursorMove::Home => 0,
+            CursorMove::End => self.letters.iter().rposition(|letter| letter.value != BLANK).map_or(0, |i| i + 1),
+            _ => return GameUpdateResult::InvalidInput
+        };
+        self.cursor = cursor;
+        GameUpdateResult::Ok
     }
+}
 
+impl Guess for PendingGuess {
     fn letters(&self) -> &Vec<GuessedLetter> {
         &self.letters
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_state::GameUpdateResult;
+    use crate::guess::{CursorMove, PendingGuess};
+
+    fn typed(word: &str) -> PendingGuess {
+        let mut guess = PendingGuess::new("crane".to_string());
+        for letter in word.chars() {
+            guess.push_letter(letter);
+        }
+        guess
+    }
+
+    #[test]
+    fn should_overwrite_letter_under_cursor() {
+        let mut guess = typed("crone");
+        guess.move_cursor(CursorMove::Home);
+        guess.move_cursor(CursorMove::Right);
+        guess.move_cursor(CursorMove::Right);
+        guess.push_letter('a');
+        assert_eq!(guess.word(), "crane");
+        assert_eq!(guess.cursor(), 3);
+    }
+
+    #[test]
+    fn should_clear_cell_without_moving_others() {
+        let mut guess = typed("crane");
+        guess.move_cursor(CursorMove::Home);
+        assert_eq!(guess.delete_letter(), GameUpdateResult::Ok);
+        assert_eq!(guess.word(), " rane");
+        assert!(!guess.full());
+        assert_eq!(guess.delete_letter(), GameUpdateResult::InvalidInput);
+    }
+
+    #[test]
+    fn should_move_to_end_of_typed_letters() {
+        let mut guess = typed("cr");
+        guess.move_cursor(CursorMove::Home);
+        assert_eq!(guess.move_cursor(CursorMove::Left), GameUpdateResult::InvalidInput);
+        guess.move_cursor(CursorMove::End);
+        assert_eq!(guess.cursor(), 2);
+        assert_eq!(guess.pop_letter(), GameUpdateResult::Ok);
+        assert_eq!(guess.word(), "c    ");
+    }
+}
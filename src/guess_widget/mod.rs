@@ -1,16 +1,27 @@
 use ratatui::prelude::*;
-use ratatui::layout::Flex;
+use crate::animation::Animation;
+use crate::board_layout::Tiles;
 use crate::game_state::GameState;
 use crate::letter_box_widget::LetterBoxWidget;
-use crate::letters::GuessedLetter;
+use crate::letters::{GuessedLetter, LetterState};
+use crate::theme::Theme;
 
 pub struct GuessWidget<'a>{
     game_state: &'a GameState,
+    theme: &'a Theme,
+    tiles: Tiles,
+    animation: Option<&'a Animation>,
+    marked: bool
 }
 
 impl <'a> GuessWidget<'a> {
-    pub fn new(game_state: &'a mut GameState) -> Self {
-        Self{ game_state }
+    pub fn new(game_state: &'a mut GameState, theme: &'a Theme, tiles: Tiles, animation: Option<&'a Animation>) -> Self {
+        Self{ game_state, theme, tiles, animation, marked: false }
+    }
+
+    pub fn marked(mut self, marked: bool) -> Self {
+        self.marked = marked;
+        self
     }
 
 }
@@ -19,46 +30,81 @@ impl <'a> Widget for GuessWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) where Self: Sized {
 
         let max_tries = self.game_state.max_tries;
-        let word_len = self.game_state.target_word.len();
-
-        let max_height = (area.height / max_tries as u16).max(2) - 1;
-
-        let lines = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints((0..max_tries).into_iter().map(|_| Constraint::Length(max_height)).collect::<Vec<_>>())
-            .spacing(1)
-            .split(area);
+        let word_len = self.game_state.word_len();
+        let cursor = self.game_state.cursor().map(|cursor| (self.game_state.guess_count(), cursor));
 
         for line_idx in 0..max_tries as usize {
 
             let guess = self.game_state.get(line_idx);
             let letters = guess.letters();
+            let letter_boxes = self.tiles.row(area, line_idx, word_len);
 
-            let line = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints(vec![
-                    Constraint::Percentage(35),
-                    Constraint::Percentage(30),
-                    Constraint::Percentage(35)
-                ])
-                .spacing(1)
-                .split(lines[line_idx]);
-
-            let letter_boxes = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints((0..word_len).into_iter().map(|_| Constraint::Ratio(1, word_len as u32)).collect::<Vec<_>>())
-                .flex(Flex::SpaceBetween)
-                .spacing(8)
-                .split(line[1]);
-
-            for letter_idx in 0..word_len {
+            for (letter_idx, letter_box) in letter_boxes.into_iter().enumerate() {
 
                 let letter = letters.get(letter_idx).cloned().unwrap_or(GuessedLetter::default());
+                let effect = self.animation.map(|animation| animation.tile(line_idx, letter_idx)).unwrap_or_default();
+                let state = if effect.hidden { LetterState::NotChecked } else { letter.state };
 
-                LetterBoxWidget::new(letter.value, letter.state)
-                    .render(letter_boxes[letter_idx], buf);
+                LetterBoxWidget::new(letter.value, state, self.theme)
+                    .highlighted(cursor == Some((line_idx, letter_idx)))
+                    .marked(self.marked)
+                    .render(effect.apply(letter_box, area), buf);
 
             }
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use ratatui::buffer::Buffer;
+    use ratatui::prelude::*;
+    use crate::animation::{Animation, AnimationKind};
+    use crate::board_layout::Tiles;
+    use crate::game_state::GameState;
+    use crate::guess_widget::GuessWidget;
+    use crate::language::Language;
+    use crate::snapshot::assert_snapshot;
+    use crate::theme::Theme;
+
+    const TILES: Tiles = Tiles { width: 3, height: 1, gap: 1, row_gap: 0 };
+
+    fn game(target: &str, tries: u8, guesses: &[&str], pending: &str) -> GameState {
+        let mut state = GameState::new(target, tries, &Language::english().alphabet);
+        for guess in guesses {
+            guess.chars().for_each(|letter| { state.push_letter(letter); });
+            state.commit_guess();
+        }
+        pending.chars().for_each(|letter| { state.push_letter(letter); });
+        state
+    }
+
+    fn render(state: &mut GameState, animation: Option<&Animation>) -> Buffer {
+        let theme = Theme::dark();
+        let area = Rect::new(0, 0, TILES.width(state.word_len()), TILES.height(state.max_tries as usize));
+        let mut buffer = Buffer::empty(area);
+        GuessWidget::new(state, &theme, TILES, animation).render(area, &mut buffer);
+        buffer
+    }
+
+    #[test]
+    fn should_render_guesses_and_pending_word_with_cursor() {
+        let mut state = game("crane", 6, &["trace", "cable"], "cr");
+        assert_snapshot("guess_widget_five_letters", &render(&mut state, None));
+    }
+
+    #[test]
+    fn should_render_other_word_lengths() {
+        let mut state = game("word", 4, &["wore"], "");
+        assert_snapshot("guess_widget_four_letters", &render(&mut state, None));
+
+        let mut state = game("letters", 7, &["settler", "letters"], "");
+        assert_snapshot("guess_widget_seven_letters", &render(&mut state, None));
+    }
+
+    #[test]
+    fn should_hide_colours_of_tiles_not_flipped_yet() {
+        let mut state = game("crane", 6, &["trace"], "");
+        let animation = Animation::new(AnimationKind::Flip, 0, 5);
+        assert_snapshot("guess_widget_flipping", &render(&mut state, Some(&animation)));
+    }
+}
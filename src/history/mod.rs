@@ -0,0 +1,74 @@
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use crate::paths;
+
+const HISTORY_FILE: &str = "history.txt";
+
+pub struct History {
+    path: Option<PathBuf>,
+    answers: Vec<String>
+}
+
+impl History {
+
+    pub fn load() -> Self {
+        let path = paths::data_dir().map(|dir| dir.join(HISTORY_FILE));
+        let answers = path.as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|contents| contents.lines().map(str::trim).filter(|l| !l.is_empty()).map(str::to_string).collect())
+            .unwrap_or_default();
+
+        Self { path, answers }
+    }
+
+    // Nothing is read or written, e.g. for games played by bots.
+    pub fn in_memory() -> Self {
+        Self { path: None, answers: Vec::new() }
+    }
+
+    pub fn record(&mut self, answer: &str) -> io::Result<()> {
+        self.answers.push(answer.to_string());
+
+        match &self.path {
+            Some(path) => {
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+                writeln!(file, "{}", answer)
+            },
+            None => Ok(())
+        }
+    }
+
+    pub fn recent(&self, window: usize) -> HashSet<&str> {
+        self.answers.iter().rev().take(window).map(String::as_str).collect()
+    }
+
+    // Games played since the answer last came up, `None` if it was never played.
+    pub fn games_since(&self, answer: &str) -> Option<usize> {
+        self.answers.iter().rev().position(|played| played == answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::history::History;
+
+    #[test]
+    fn should_only_report_answers_within_window() {
+        let mut history = History::in_memory();
+        for answer in ["crane", "slate", "trace"] {
+            history.record(answer).unwrap();
+        }
+
+        let recent = history.recent(2);
+        assert!(!recent.contains("crane"));
+        assert!(recent.contains("slate"));
+        assert!(recent.contains("trace"));
+        assert_eq!(history.games_since("crane"), Some(2));
+        assert_eq!(history.games_since("plant"), None);
+    }
+}
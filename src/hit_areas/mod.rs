@@ -0,0 +1,75 @@
+use ratatui::layout::{Position, Rect};
+
+// What a mouse click on a rendered area stands for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    Key(char),
+    Enter,
+    Backspace,
+    Entry(usize)
+}
+
+// Areas recorded by widgets while rendering, looked up when the mouse is clicked.
+#[derive(Clone, Debug, Default)]
+pub struct HitAreas {
+    areas: Vec<(Rect, Target)>
+}
+
+impl HitAreas {
+
+    pub fn add(&mut self, area: Rect, target: Target) {
+        self.areas.push((area, target));
+    }
+
+    pub fn target_at(&self, column: u16, row: u16) -> Option<Target> {
+        let position = Position { x: column, y: row };
+        self.areas.iter()
+            .find(|(area, _)| area.contains(position))
+            .map(|(_, target)| *target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::widgets::Widget;
+    use crate::board_layout::Tiles;
+    use crate::hit_areas::{HitAreas, Target};
+    use crate::language::Language;
+    use crate::letters::Letters;
+    use crate::letters_widget::LettersWidget;
+    use crate::theme::Theme;
+
+    #[test]
+    fn should_find_clicked_target() {
+        let mut hit_areas = HitAreas::default();
+        hit_areas.add(Rect::new(0, 0, 3, 1), Target::Key('q'));
+        hit_areas.add(Rect::new(4, 0, 3, 1), Target::Key('w'));
+
+        assert_eq!(hit_areas.target_at(5, 0), Some(Target::Key('w')));
+        assert_eq!(hit_areas.target_at(3, 0), None);
+        assert_eq!(hit_areas.target_at(0, 1), None);
+    }
+
+    #[test]
+    fn should_click_keys_of_the_rendered_keyboard() {
+        let language = Language::english();
+        let theme = Theme::dark();
+        let tiles = Tiles { width: 3, height: 1, gap: 1, row_gap: 0 };
+        let mut hit_areas = HitAreas::default();
+        let widget = LettersWidget::new(Letters::new(&language.alphabet), &language.layout, &theme)
+            .tiles(tiles)
+            .hit_areas(&mut hit_areas);
+        let area = Rect::new(0, 0, tiles.width(widget.columns()), tiles.height(widget.rows()));
+        widget.render(area, &mut Buffer::empty(area));
+
+        // Q starts two columns in, ENTER fills the first seven columns of the last row and ⌫ ends it.
+        assert_eq!(hit_areas.target_at(3, 0), Some(Target::Key('q')));
+        assert_eq!(hit_areas.target_at(0, 2), Some(Target::Enter));
+        assert_eq!(hit_areas.target_at(6, 2), Some(Target::Enter));
+        assert_eq!(hit_areas.target_at(area.width - 1, 2), Some(Target::Backspace));
+        assert_eq!(hit_areas.target_at(7, 2), None);
+        assert_eq!(hit_areas.target_at(1, 0), None);
+    }
+}
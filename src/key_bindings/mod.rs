@@ -0,0 +1,408 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use crate::action::Action;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyCode {
+    Char(char),
+    Enter,
+    Esc,
+    Backspace,
+    Delete,
+    Tab,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    F(u8)
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool
+}
+
+// A key press, independent of the terminal library reporting it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: Modifiers
+}
+
+impl Key {
+    pub fn new(code: KeyCode, modifiers: Modifiers) -> Self {
+        // Shift is part of the typed character, so Shift+a is the key typing A, whichever way the
+        // terminal reports it. It's only kept as a modifier for non-character keys.
+        match code {
+            KeyCode::Char(c) => {
+                let c = if modifiers.shift { c.to_uppercase().next().unwrap_or(c) } else { c };
+                Self { code: KeyCode::Char(c), modifiers: Modifiers { shift: false, ..modifiers } }
+            },
+            _ => Self { code, modifiers }
+        }
+    }
+
+    // A character typed without Ctrl or Alt.
+    fn typed(&self) -> Option<char> {
+        match self.code {
+            KeyCode::Char(c) if !self.modifiers.ctrl && !self.modifiers.alt => Some(c),
+            _ => None
+        }
+    }
+}
+
+impl From<KeyCode> for Key {
+    fn from(code: KeyCode) -> Self {
+        Key::new(code, Modifiers::default())
+    }
+}
+
+impl FromStr for Key {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_lowercase();
+        let (modifiers, key) = match lower.rsplit_once('+') {
+            Some((modifiers, key)) if !key.is_empty() => (modifiers, key),
+            _ => ("", lower.as_str())
+        };
+
+        let mut key_modifiers = Modifiers::default();
+        for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+            match modifier {
+                "ctrl" => key_modifiers.ctrl = true,
+                "alt" => key_modifiers.alt = true,
+                "shift" => key_modifiers.shift = true,
+                other => return Err(format!("Unknown modifier '{}' in key '{}'", other, s))
+            };
+        }
+
+        let code = match key {
+            "esc" | "escape" => KeyCode::Esc,
+            "enter" | "return" => KeyCode::Enter,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "tab" => KeyCode::Tab,
+            "space" => KeyCode::Char(' '),
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            function if function.len() > 1 && function.starts_with('f') => match function[1..].parse::<u8>() {
+                Ok(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                _ => return Err(format!("Unknown key '{}'", s))
+            },
+            other => {
+                let mut chars = other.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return Err(format!("Unknown key '{}'", s))
+                }
+            }
+        };
+
+        Ok(Key::new(code, key_modifiers))
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.modifiers.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.alt {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.shift {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Delete"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::Up => write!(f, "Up"),
+            KeyCode::Down => write!(f, "Down"),
+            KeyCode::Left => write!(f, "Left"),
+            KeyCode::Right => write!(f, "Right"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) if c.is_uppercase() => write!(f, "Shift+{}", c),
+            KeyCode::Char(c) => write!(f, "{}", c.to_uppercase())
+        }
+    }
+}
+
+// A single key or a list of keys bound to the same action.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Keys {
+    One(String),
+    Many(Vec<String>)
+}
+
+impl Keys {
+    fn names(&self) -> Vec<&str> {
+        match self {
+            Keys::One(key) => vec![key.as_str()],
+            Keys::Many(keys) => keys.iter().map(String::as_str).collect()
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeysConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quit: Option<Keys>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub submit: Option<Keys>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete: Option<Keys>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clear: Option<Keys>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub left: Option<Keys>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right: Option<Keys>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub up: Option<Keys>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub down: Option<Keys>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub home: Option<Keys>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<Keys>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restart: Option<Keys>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<Keys>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<Keys>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help: Option<Keys>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<Keys>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settings: Option<Keys>
+}
+
+impl KeysConfig {
+    pub fn is_empty(&self) -> bool {
+        *self == KeysConfig::default()
+    }
+
+    fn bindings(&self) -> [(Action, &Option<Keys>); 16] {
+        [
+            (Action::Quit, &self.quit),
+            (Action::Submit, &self.submit),
+            (Action::Delete, &self.delete),
+            (Action::Clear, &self.clear),
+            (Action::Left, &self.left),
+            (Action::Right, &self.right),
+            (Action::Up, &self.up),
+            (Action::Down, &self.down),
+            (Action::Home, &self.home),
+            (Action::End, &self.end),
+            (Action::Restart, &self.restart),
+            (Action::Hint, &self.hint),
+            (Action::ShowStats, &self.stats),
+            (Action::Help, &self.help),
+            (Action::Theme, &self.theme),
+            (Action::Settings, &self.settings)
+        ]
+    }
+}
+
+const DEFAULT_PRESET: &str = "default";
+const VIM_PRESET: &str = "vim";
+
+const DEFAULT_KEYS: [(Action, &[&str]); 16] = [
+    (Action::Quit, &["esc"]),
+    (Action::Submit, &["enter"]),
+    (Action::Delete, &["backspace"]),
+    (Action::Clear, &["delete"]),
+    (Action::Left, &["left"]),
+    (Action::Right, &["right"]),
+    (Action::Up, &["up"]),
+    (Action::Down, &["down"]),
+    (Action::Home, &["home"]),
+    (Action::End, &["end"]),
+    (Action::Help, &["f1"]),
+    (Action::Theme, &["f2"]),
+    (Action::Settings, &["f3"]),
+    (Action::ShowStats, &["f4"]),
+    (Action::Restart, &["f5"]),
+    (Action::Hint, &["f6"])
+];
+
+// Letters type while guessing, so h/j/k/l only move around menus and need Ctrl in the game.
+const VIM_KEYS: [(Action, &[&str]); 8] = [
+    (Action::Left, &["h", "ctrl+h"]),
+    (Action::Down, &["j", "ctrl+j"]),
+    (Action::Up, &["k", "ctrl+k"]),
+    (Action::Right, &["l", "ctrl+l"]),
+    (Action::Home, &["0", "^"]),
+    (Action::End, &["$"]),
+    (Action::Clear, &["ctrl+x"]),
+    (Action::Help, &["?"])
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyBindings {
+    bindings: Vec<(Key, Action)>
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings::preset(DEFAULT_PRESET).unwrap_or(KeyBindings { bindings: Vec::new() })
+    }
+}
+
+impl KeyBindings {
+
+    // The vim preset adds its keys on top of the default ones.
+    fn preset(name: &str) -> Option<Self> {
+        let layers: &[&[(Action, &[&str])]] = match name {
+            DEFAULT_PRESET => &[&DEFAULT_KEYS],
+            VIM_PRESET => &[&DEFAULT_KEYS, &VIM_KEYS],
+            _ => return None
+        };
+
+        let mut bindings = KeyBindings { bindings: Vec::new() };
+        for (action, keys) in layers.iter().flat_map(|layer| layer.iter()) {
+            for key in keys.iter().filter_map(|key| key.parse().ok()) {
+                bindings.bind(key, *action);
+            }
+        }
+        Some(bindings)
+    }
+
+    // A key triggers a single action, so binding it again replaces its previous action.
+    fn bind(&mut self, key: Key, action: Action) {
+        self.bindings.retain(|(bound, _)| *bound != key);
+        self.bindings.push((key, action));
+    }
+
+    // Configured actions replace every key of the preset. Invalid keys and presets are reported back.
+    pub fn configure(config: &KeysConfig) -> (Self, Vec<String>) {
+        let mut errors = Vec::new();
+
+        let preset = config.preset.as_deref().unwrap_or(DEFAULT_PRESET);
+        let mut bindings = KeyBindings::preset(preset).unwrap_or_else(|| {
+            errors.push(format!("Unknown key preset '{}', expected one of: {}, {}", preset, DEFAULT_PRESET, VIM_PRESET));
+            KeyBindings::default()
+        });
+
+        for (action, keys) in config.bindings() {
+            let keys: Vec<Key> = match keys {
+                Some(keys) => keys.names().into_iter()
+                    .filter_map(|key| key.parse().map_err(|error| errors.push(error)).ok())
+                    .collect(),
+                None => continue
+            };
+
+            if !keys.is_empty() {
+                bindings.bindings.retain(|(_, bound)| *bound != action);
+                for key in keys {
+                    bindings.bind(key, action);
+                }
+            }
+        }
+
+        (bindings, errors)
+    }
+
+    // While a guess is being typed, letters always type, whatever they're bound to.
+    pub fn action(&self, key: &Key, typing: bool) -> Option<Action> {
+        let typed = key.typed().map(|c| c.to_lowercase().next().unwrap_or(c));
+
+        match typed {
+            Some(c) if typing && c.is_alphabetic() => Some(Action::Type(c)),
+            _ => self.bindings.iter()
+                .find(|(bound, _)| bound == key)
+                .map(|(_, action)| *action)
+                .or(typed.map(Action::Type))
+        }
+    }
+
+    pub fn key(&self, action: Action) -> Option<Key> {
+        self.bindings.iter().find(|(_, bound)| *bound == action).map(|(key, _)| *key)
+    }
+
+    // Name of the first key of the action, shown in help texts.
+    pub fn label(&self, action: Action) -> String {
+        self.key(action).map(|key| key.to_string()).unwrap_or_else(|| "-".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::Action;
+    use crate::key_bindings::{Key, KeyBindings, KeyCode, Keys, KeysConfig, Modifiers};
+
+    fn ctrl(c: char) -> Key {
+        Key::new(KeyCode::Char(c), Modifiers { ctrl: true, ..Modifiers::default() })
+    }
+
+    #[test]
+    fn should_parse_keys_with_modifiers() {
+        let key: Key = "Ctrl+Q".parse().unwrap();
+        assert_eq!(key, ctrl('q'));
+        assert_eq!(key.to_string(), "Ctrl+Q");
+    }
+
+    #[test]
+    fn should_match_shifted_letters_however_they_are_reported() {
+        let shift = Modifiers { shift: true, ..Modifiers::default() };
+        let config = KeysConfig { hint: Some(Keys::One("shift+h".to_string())), ..KeysConfig::default() };
+        let (bindings, errors) = KeyBindings::configure(&config);
+        assert!(errors.is_empty());
+
+        assert_eq!(bindings.action(&Key::new(KeyCode::Char('h'), shift), false), Some(Action::Hint));
+        assert_eq!(bindings.action(&Key::new(KeyCode::Char('H'), shift), false), Some(Action::Hint));
+        assert_eq!(bindings.action(&Key::from(KeyCode::Char('H')), false), Some(Action::Hint));
+        assert_eq!(bindings.action(&Key::from(KeyCode::Char('h')), false), Some(Action::Type('h')));
+        assert_eq!(bindings.label(Action::Hint), "Shift+H");
+    }
+
+    #[test]
+    fn should_keep_defaults_for_invalid_keys() {
+        let config = KeysConfig {
+            quit: Some(Keys::One("f13".to_string())),
+            delete: Some(Keys::Many(vec!["delete".to_string(), "ctrl+d".to_string()])),
+            ..KeysConfig::default()
+        };
+        let (bindings, errors) = KeyBindings::configure(&config);
+        assert_eq!(bindings.label(Action::Quit), "Esc");
+        assert_eq!(bindings.action(&ctrl('d'), true), Some(Action::Delete));
+        assert_eq!(bindings.action(&Key::from(KeyCode::Delete), true), Some(Action::Delete));
+        assert_eq!(bindings.action(&Key::from(KeyCode::Backspace), true), None);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn should_type_letters_while_guessing_even_when_bound() {
+        let (bindings, errors) = KeyBindings::configure(&KeysConfig { preset: Some("vim".to_string()), ..KeysConfig::default() });
+        assert!(errors.is_empty());
+
+        let j = Key::from(KeyCode::Char('j'));
+        assert_eq!(bindings.action(&j, false), Some(Action::Down));
+        assert_eq!(bindings.action(&j, true), Some(Action::Type('j')));
+        assert_eq!(bindings.action(&ctrl('j'), true), Some(Action::Down));
+        assert_eq!(bindings.action(&Key::from(KeyCode::Char('$')), true), Some(Action::End));
+        assert_eq!(bindings.action(&Key::from(KeyCode::Char('Ł')), true), Some(Action::Type('ł')));
+    }
+}
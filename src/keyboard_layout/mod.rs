@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyboardLayout {
+    pub name: String,
+    pub rows: Vec<Vec<char>>
+}
+
+const ALPHABETICAL: &str = "alphabetical";
+const ALPHABETICAL_ROW_LEN: usize = 9;
+
+const BUILT_IN: [(&str, [&str; 3]); 5] = [
+    ("qwerty", ["qwertyuiop", "asdfghjkl", "zxcvbnm"]),
+    ("azerty", ["azertyuiop", "qsdfghjklm", "wxcvbn"]),
+    ("qwertz", ["qwertzuiop", "asdfghjkl", "yxcvbnm"]),
+    ("dvorak", ["pyfgcrl", "aoeuidhtns", "qjkxbmwvz"]),
+    ("colemak", ["qwfpgjluy", "arstdhneio", "zxcvbkm"]),
+];
+
+impl KeyboardLayout {
+
+    pub fn new(name: &str, rows: &[String]) -> Self {
+        Self {
+            name: name.to_string(),
+            rows: rows.iter().map(|row| row.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect()).collect()
+        }
+    }
+
+    pub fn alphabetical(alphabet: &[char]) -> Self {
+        Self {
+            name: ALPHABETICAL.to_string(),
+            rows: alphabet.chunks(ALPHABETICAL_ROW_LEN).map(|chunk| chunk.to_vec()).collect()
+        }
+    }
+
+    pub fn names(user_layouts: &BTreeMap<String, Vec<String>>) -> Vec<String> {
+        BUILT_IN.iter().map(|(name, _)| name.to_string())
+            .chain([ALPHABETICAL.to_string()])
+            .chain(user_layouts.keys().cloned())
+            .collect()
+    }
+
+    // User layouts from the config take precedence over the built-in ones.
+    pub fn find(name: &str, alphabet: &[char], user_layouts: &BTreeMap<String, Vec<String>>) -> Result<Self, String> {
+        let name = name.to_lowercase();
+
+        if let Some(rows) = user_layouts.get(&name) {
+            return Ok(KeyboardLayout::new(&name, rows));
+        }
+
+        if name == ALPHABETICAL {
+            return Ok(KeyboardLayout::alphabetical(alphabet));
+        }
+
+        BUILT_IN.iter()
+            .find(|(built_in, _)| *built_in == name)
+            .map(|(name, rows)| KeyboardLayout::new(name, &rows.map(str::to_string)))
+            .ok_or_else(|| format!("Unknown keyboard layout '{}', expected one of: {}", name, KeyboardLayout::names(user_layouts).join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use crate::keyboard_layout::KeyboardLayout;
+    use crate::language::Language;
+
+    #[test]
+    fn should_have_every_english_letter_on_built_in_layouts() {
+        let alphabet = Language::english().alphabet;
+        for name in KeyboardLayout::names(&BTreeMap::new()) {
+            let layout = KeyboardLayout::find(&name, &alphabet, &BTreeMap::new()).unwrap();
+            let keys: Vec<char> = layout.rows.concat();
+            assert_eq!(keys.len(), alphabet.len(), "{} has duplicated or missing keys", name);
+            assert!(alphabet.iter().all(|c| keys.contains(c)), "{} is missing letters", name);
+        }
+    }
+
+    #[test]
+    fn should_prefer_user_layouts() {
+        let user_layouts = BTreeMap::from([("qwerty".to_string(), vec!["Q W E".to_string()])]);
+        let layout = KeyboardLayout::find("QWERTY", &[], &user_layouts).unwrap();
+        assert_eq!(layout.rows, vec![vec!['q', 'w', 'e']]);
+    }
+
+    #[test]
+    fn should_reject_unknown_layouts() {
+        assert!(KeyboardLayout::find("bepo", &[], &BTreeMap::new()).is_err());
+    }
+}
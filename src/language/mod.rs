@@ -0,0 +1,455 @@
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use crate::action::Action;
+use crate::difficulty::Difficulty;
+use crate::game_state::Rejection;
+use crate::key_bindings::KeyBindings;
+use crate::word_source::PickError;
+use crate::words::WordPack;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Messages {
+    pub help: &'static str,
+    pub won: &'static str,
+    pub lost: &'static str,
+    pub record: &'static str,
+    pub failure: &'static str,
+    pub failure_hint: &'static str,
+    pub loading: &'static str,
+    pub fetch_failed: &'static str,
+    pub no_words: &'static str,
+    pub no_words_at_all: &'static str,
+    pub easy: &'static str,
+    pub normal: &'static str,
+    pub hard: &'static str,
+    pub whole_list: &'static str,
+    pub invalid_config: &'static str,
+    pub settings_title: &'static str,
+    pub settings_help: &'static str,
+    pub word_length: &'static str,
+    pub tries: &'static str,
+    pub hard_mode: &'static str,
+    pub difficulty_label: &'static str,
+    pub language: &'static str,
+    pub words: &'static str,
+    pub theme: &'static str,
+    pub keyboard: &'static str,
+    pub animations: &'static str,
+    pub bell: &'static str,
+    pub accessible: &'static str,
+    pub last_guess: &'static str,
+    pub known_letters: &'static str,
+    pub no_guesses: &'static str,
+    pub correct: &'static str,
+    pub elsewhere: &'static str,
+    pub absent: &'static str,
+    pub none: &'static str,
+    pub too_small: &'static str,
+    pub hint: &'static str,
+    pub not_enough_letters: &'static str,
+    pub not_in_word_list: &'static str,
+    pub must_contain: &'static str,
+    pub must_be_at: &'static str,
+    pub on: &'static str,
+    pub off: &'static str,
+    pub default: &'static str,
+    pub menu_title: &'static str,
+    pub menu_help: &'static str,
+    pub menu_continue: &'static str,
+    pub menu_new_game: &'static str,
+    pub menu_statistics: &'static str,
+    pub menu_settings: &'static str,
+    pub menu_help_item: &'static str,
+    pub menu_about: &'static str,
+    pub menu_quit: &'static str,
+    pub new_game_title: &'static str,
+    pub new_game_help: &'static str,
+    pub statistics_title: &'static str,
+    pub played: &'static str,
+    pub won_games: &'static str,
+    pub win_rate: &'static str,
+    pub guess_distribution: &'static str,
+    pub no_games: &'static str,
+    pub help_title: &'static str,
+    pub rules: &'static str,
+    pub about_title: &'static str,
+    pub about: &'static str,
+    pub back: &'static str,
+    pub line_intro: &'static str,
+    pub line_prompt: &'static str,
+    pub line_won: &'static str,
+    pub line_lost: &'static str,
+    pub line_play_again: &'static str,
+    pub line_yes: &'static str,
+    pub line_wrong_length: &'static str,
+    pub line_not_a_letter: &'static str,
+    pub green: &'static str,
+    pub yellow: &'static str,
+    pub grey: &'static str,
+    pub blank: &'static str
+}
+
+const ENGLISH_MESSAGES: Messages = Messages {
+    help: "Type a word. {submit} guesses, {delete} clears, {theme} theme, {settings} settings, {quit} menu.",
+    won: "You won! {record}. {submit} starts over.",
+    lost: "You lost, it was '{word}'. {record}. {submit} starts over.",
+    record: "{difficulty}: {won}/{played} won ({rate}%)",
+    failure: "Error: {message}. Press {submit} to retry. {settings} for settings. {quit} for the menu.",
+    failure_hint: "Press {submit} or {quit} to continue. {settings} for settings.",
+    loading: "Loading...",
+    fetch_failed: "Failed to fetch your word",
+    no_words: "No {length}-letter {difficulty} words in pack '{pack}'",
+    no_words_at_all: "No {length}-letter words in pack '{pack}'",
+    easy: "easy",
+    normal: "normal",
+    hard: "hard",
+    whole_list: "whole list",
+    invalid_config: "Some settings are invalid and were replaced with defaults:",
+    settings_title: "Settings",
+    settings_help: "{up}/{down} to choose, {left}/{right} to change, {submit} to save, {quit} to cancel. Changing the words or tries starts a new game.",
+    word_length: "Word length",
+    tries: "Tries",
+    hard_mode: "Hard mode",
+    difficulty_label: "Difficulty",
+    language: "Language",
+    words: "Words",
+    theme: "Theme",
+    keyboard: "Keyboard",
+    animations: "Animations",
+    bell: "Bell",
+    accessible: "Accessible",
+    last_guess: "Last guess {word}: {letters}.",
+    known_letters: "Correct: {correct}. Elsewhere: {elsewhere}. Absent: {absent}.",
+    no_guesses: "No guesses yet.",
+    correct: "correct",
+    elsewhere: "elsewhere",
+    absent: "absent",
+    none: "none",
+    hint: "Letter {position} is {letter}",
+    too_small: "The terminal is too small ({width}×{height}). Make it at least {min_width}×{min_height}.",
+    not_enough_letters: "Not enough letters",
+    not_in_word_list: "Not in word list",
+    must_contain: "Guess must contain {letter}",
+    must_be_at: "Letter {position} must be {letter}",
+    on: "on",
+    off: "off",
+    default: "default",
+    menu_title: "Wordle",
+    menu_help: "{up}/{down} to choose, {submit} to confirm, {quit} to go back.",
+    menu_continue: "Continue",
+    menu_new_game: "New game",
+    menu_statistics: "Statistics",
+    menu_settings: "Settings",
+    menu_help_item: "Help",
+    menu_about: "About",
+    menu_quit: "Quit",
+    new_game_title: "New game",
+    new_game_help: "{up}/{down} to choose, {left}/{right} to change, {submit} to start, {quit} to go back.",
+    statistics_title: "Statistics",
+    played: "Played",
+    won_games: "Won",
+    win_rate: "Win %",
+    guess_distribution: "Guesses in won {difficulty} games",
+    no_games: "No games played yet.",
+    help_title: "Help",
+    rules: "Guess the hidden word in as few tries as you can.\nEach guess must be a full word. After every guess the letters change colour:\na letter in the right spot, a letter in the word but in another spot, and a letter not in the word.\nIn hard mode every revealed hint has to be used in the following guesses.\n\n{submit} submits a guess, {delete} clears a letter, {left}/{right}, {home} and {end} move the cursor to retype a letter, {clear} clears the letter under it, {hint} reveals a letter, {restart} starts over, {stats} shows statistics, {help} shows this help, {theme} changes the theme, {settings} opens settings and {quit} opens the menu.",
+    about_title: "About",
+    about: "Wordle {version}\nA terminal word guessing game.\nWords come from the random word API or from word packs.",
+    back: "Press {quit} to go back.",
+    line_intro: "Guess the {length}-letter word in {tries} tries. Type '{hint}' for a hint or '{quit}' to stop.",
+    line_prompt: "Guess {guess}/{tries}: ",
+    line_won: "Solved in {guesses}/{tries}!",
+    line_lost: "Out of tries, the word was {word}.",
+    line_play_again: "Play again? [y/N] ",
+    line_yes: "y",
+    line_wrong_length: "Expected a {length}-letter word, got {count} letters.",
+    line_not_a_letter: "'{letter}' isn't a letter of this game.",
+    green: "green",
+    yellow: "yellow",
+    grey: "grey",
+    blank: "blank"
+};
+
+const POLISH_MESSAGES: Messages = Messages {
+    help: "{submit} zatwierdza słowo, {delete} usuwa, {theme} motyw, {settings} ustawienia, {quit} menu.",
+    won: "Wygrana! {record}. {submit} od nowa.",
+    lost: "Przegrana! Słowo: '{word}'. {record}. {submit} od nowa.",
+    record: "{difficulty}: {won}/{played} wygr. ({rate}%)",
+    failure: "Błąd: {message}. Naciśnij {submit}, aby spróbować ponownie. {settings} otwiera ustawienia. {quit} otwiera menu.",
+    failure_hint: "Naciśnij {submit} lub {quit}, aby kontynuować. {settings} otwiera ustawienia.",
+    loading: "Ładowanie...",
+    fetch_failed: "Nie udało się pobrać słowa",
+    no_words: "Brak {length}-literowych słów na poziomie {difficulty} w zestawie '{pack}'",
+    no_words_at_all: "Brak {length}-literowych słów w zestawie '{pack}'",
+    easy: "łatwy",
+    normal: "normalny",
+    hard: "trudny",
+    whole_list: "cała lista",
+    invalid_config: "Niektóre ustawienia są błędne i zastąpiono je domyślnymi:",
+    settings_title: "Ustawienia",
+    settings_help: "{up}/{down} wybiera, {left}/{right} zmienia, {submit} zapisuje, {quit} anuluje. Zmiana słów lub liczby prób zaczyna nową grę.",
+    word_length: "Długość słowa",
+    tries: "Próby",
+    hard_mode: "Tryb trudny",
+    difficulty_label: "Poziom",
+    language: "Język",
+    words: "Słowa",
+    theme: "Motyw",
+    keyboard: "Klawiatura",
+    animations: "Animacje",
+    bell: "Dzwonek",
+    accessible: "Dostępność",
+    last_guess: "Ostatnia próba {word}: {letters}.",
+    known_letters: "Trafione: {correct}. Gdzie indziej: {elsewhere}. Brak: {absent}.",
+    no_guesses: "Nie było jeszcze prób.",
+    correct: "trafiona",
+    elsewhere: "gdzie indziej",
+    absent: "brak",
+    none: "brak",
+    hint: "Litera {position} to {letter}",
+    too_small: "Terminal jest za mały ({width}×{height}). Powiększ go do co najmniej {min_width}×{min_height}.",
+    not_enough_letters: "Za mało liter",
+    not_in_word_list: "Nie ma takiego słowa na liście",
+    must_contain: "Słowo musi zawierać {letter}",
+    must_be_at: "Litera {position} musi być {letter}",
+    on: "wł.",
+    off: "wył.",
+    default: "domyślne",
+    menu_title: "Wordle",
+    menu_help: "{up}/{down} wybiera, {submit} zatwierdza, {quit} wraca.",
+    menu_continue: "Kontynuuj",
+    menu_new_game: "Nowa gra",
+    menu_statistics: "Statystyki",
+    menu_settings: "Ustawienia",
+    menu_help_item: "Pomoc",
+    menu_about: "O grze",
+    menu_quit: "Wyjście",
+    new_game_title: "Nowa gra",
+    new_game_help: "{up}/{down} wybiera, {left}/{right} zmienia, {submit} zaczyna grę, {quit} wraca.",
+    statistics_title: "Statystyki",
+    played: "Gry",
+    won_games: "Wygrane",
+    win_rate: "% wygr.",
+    guess_distribution: "Liczba prób w wygranych grach na poziomie {difficulty}",
+    no_games: "Nie rozegrano jeszcze żadnej gry.",
+    help_title: "Pomoc",
+    rules: "Odgadnij ukryte słowo w jak najmniejszej liczbie prób.\nKażda próba musi być pełnym słowem. Po każdej próbie litery zmieniają kolor:\nlitera na właściwym miejscu, litera występująca w słowie na innym miejscu i litera, której nie ma w słowie.\nW trybie trudnym każda odkryta wskazówka musi zostać użyta w kolejnych próbach.\n\n{submit} zatwierdza próbę, {delete} usuwa literę, {left}/{right}, {home} i {end} przesuwają kursor, aby poprawić literę, {clear} czyści literę pod kursorem, {hint} odkrywa literę, {restart} zaczyna od nowa, {stats} pokazuje statystyki, {help} pokazuje tę pomoc, {theme} zmienia motyw, {settings} otwiera ustawienia, a {quit} otwiera menu.",
+    about_title: "O grze",
+    about: "Wordle {version}\nTerminalowa gra w zgadywanie słów.\nSłowa pochodzą z API losowych słów albo z zestawów słów.",
+    back: "Naciśnij {quit}, aby wrócić.",
+    line_intro: "Odgadnij {length}-literowe słowo w {tries} próbach. Wpisz '{hint}', aby dostać podpowiedź, lub '{quit}', aby zakończyć.",
+    line_prompt: "Próba {guess}/{tries}: ",
+    line_won: "Odgadnięte w {guesses}/{tries}!",
+    line_lost: "Koniec prób, szukane słowo to {word}.",
+    line_play_again: "Zagrać jeszcze raz? [t/N] ",
+    line_yes: "t",
+    line_wrong_length: "Oczekiwano słowa {length}-literowego, podano {count} liter.",
+    line_not_a_letter: "'{letter}' nie jest literą tej gry.",
+    green: "zielona",
+    yellow: "żółta",
+    grey: "szara",
+    blank: "pusta"
+};
+
+impl Messages {
+
+    // Replaces `{name}` placeholders of the template with the given values.
+    pub fn fill(template: &str, values: &[(&str, &dyn Display)]) -> String {
+        values.iter().fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), &value.to_string())
+        })
+    }
+
+    pub fn with_keys(&self, template: &str, keys: &KeyBindings) -> String {
+        Messages::fill(template, &[
+            ("submit", &keys.label(Action::Submit)),
+            ("delete", &keys.label(Action::Delete)),
+            ("theme", &keys.label(Action::Theme)),
+            ("settings", &keys.label(Action::Settings)),
+            ("quit", &keys.label(Action::Quit)),
+            ("restart", &keys.label(Action::Restart)),
+            ("hint", &keys.label(Action::Hint)),
+            ("stats", &keys.label(Action::ShowStats)),
+            ("help", &keys.label(Action::Help)),
+            ("clear", &keys.label(Action::Clear)),
+            ("up", &keys.label(Action::Up)),
+            ("down", &keys.label(Action::Down)),
+            ("left", &keys.label(Action::Left)),
+            ("right", &keys.label(Action::Right)),
+            ("home", &keys.label(Action::Home)),
+            ("end", &keys.label(Action::End))
+        ])
+    }
+
+    pub fn difficulty(&self, difficulty: Difficulty) -> &'static str {
+        match difficulty {
+            Difficulty::Easy => self.easy,
+            Difficulty::Normal => self.normal,
+            Difficulty::Hard => self.hard
+        }
+    }
+
+    pub fn rejection(&self, rejection: &Rejection) -> String {
+        match rejection {
+            Rejection::NotEnoughLetters => self.not_enough_letters.to_string(),
+            Rejection::NotInWordList => self.not_in_word_list.to_string(),
+            Rejection::MustContain(letter) =>
+                Messages::fill(self.must_contain, &[("letter", &letter.to_uppercase())]),
+            Rejection::MustBeAt(letter, position) =>
+                Messages::fill(self.must_be_at, &[("letter", &letter.to_uppercase()), ("position", position)])
+        }
+    }
+
+    pub fn pick_error(&self, error: &PickError) -> String {
+        match error {
+            PickError::FetchFailed => self.fetch_failed.to_string(),
+            PickError::NoWords { word_len, difficulty: Some(difficulty), pack } =>
+                Messages::fill(self.no_words, &[("length", word_len), ("difficulty", &self.difficulty(*difficulty)), ("pack", pack)]),
+            PickError::NoWords { word_len, difficulty: None, pack } =>
+                Messages::fill(self.no_words_at_all, &[("length", word_len), ("pack", pack)])
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Language {
+    pub code: String,
+    pub name: String,
+    pub alphabet: Vec<char>,
+    pub layout: Vec<Vec<char>>,
+    pub pack: String,
+    pub messages: Messages
+}
+
+const ENGLISH_ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz";
+const POLISH_ALPHABET: &str = "aąbcćdeęfghijklłmnńoóprsśtuwyzźż";
+
+const QWERTY: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+const POLISH_PROGRAMMER: [&str; 4] = ["qwertyuiop", "asdfghjkl", "zxcvbnm", "ąćęłńóśźż"];
+
+impl Language {
+
+    fn new(code: &str, name: &str, alphabet: &str, layout: &[&str], messages: Messages) -> Self {
+        Self {
+            code: code.to_string(),
+            name: name.to_string(),
+            alphabet: alphabet.chars().collect(),
+            layout: layout.iter().map(|row| row.chars().collect()).collect(),
+            pack: code.to_string(),
+            messages
+        }
+    }
+
+    pub fn all() -> Vec<Self> {
+        vec![
+            Language::english(),
+            Language::new("pl", "Polski", POLISH_ALPHABET, &POLISH_PROGRAMMER, POLISH_MESSAGES),
+        ]
+    }
+
+    pub fn english() -> Self {
+        Language::new("en", "English", ENGLISH_ALPHABET, &QWERTY, ENGLISH_MESSAGES)
+    }
+
+    pub fn by_code(code: &str) -> Option<Self> {
+        Language::all().into_iter().find(|language| language.code == code)
+    }
+
+    pub fn parse(code: &str) -> Result<Self, String> {
+        Language::by_code(code).ok_or_else(|| {
+            let codes: Vec<String> = Language::all().into_iter().map(|language| language.code).collect();
+            format!("Unknown language '{}', expected one of: {}", code, codes.join(", "))
+        })
+    }
+
+    // Custom packs get the English alphabet extended with every other letter they use.
+    pub fn for_pack(pack: &WordPack) -> Self {
+        Language::by_code(pack.name()).unwrap_or_else(|| Language::english().with_letters_of(pack))
+    }
+
+    pub fn with_letters_of(mut self, pack: &WordPack) -> Self {
+        let extra: BTreeSet<char> = pack.words().iter()
+            .flat_map(|word| word.chars())
+            .filter(|c| !self.alphabet.contains(c))
+            .collect();
+
+        self.alphabet.extend(extra);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::key_bindings::{KeyBindings, Keys, KeysConfig};
+    use crate::language::{Language, Messages};
+    use crate::words::WordPack;
+
+    #[test]
+    fn should_name_the_bound_keys_in_help_texts() {
+        let config = KeysConfig { left: Some(Keys::One("ctrl+b".to_string())), ..KeysConfig::default() };
+        let (keys, _) = KeyBindings::configure(&config);
+        for language in Language::all() {
+            let messages = &language.messages;
+            for text in [messages.help, messages.rules, messages.menu_help, messages.new_game_help, messages.settings_help, messages.back] {
+                let filled = messages.with_keys(text, &keys);
+                assert!(!filled.contains('{'), "{}", filled);
+            }
+            assert!(messages.with_keys(messages.rules, &keys).contains("Ctrl+B/Right"));
+        }
+    }
+
+    // The titles run along the keyboard's border, which a standard terminal fits in 80 columns.
+    #[test]
+    fn should_fit_game_titles_in_80_columns() {
+        let keys = KeyBindings::default();
+        for language in Language::all() {
+            let messages = &language.messages;
+            let record = Messages::fill(messages.record, &[
+                ("difficulty", &messages.normal),
+                ("won", &999),
+                ("played", &999),
+                ("rate", &100)
+            ]);
+            let titles = [messages.help, messages.won, messages.lost].map(|title| {
+                Messages::fill(&messages.with_keys(title, &keys), &[("record", &record), ("word", &"abcdefghij")])
+            });
+            for title in titles {
+                assert!(title.chars().count() <= 80, "'{}' is {} columns", title, title.chars().count());
+            }
+        }
+    }
+
+    #[test]
+    fn should_spell_embedded_packs_with_their_alphabet() {
+        for language in Language::all() {
+            let pack = WordPack::embedded(&language.pack).unwrap();
+            let foreign: Vec<&String> = pack.words().iter()
+                .filter(|word| !word.chars().all(|c| language.alphabet.contains(&c)))
+                .collect();
+            assert!(foreign.is_empty(), "{:?} aren't spelled with the {} alphabet", foreign, language.name);
+        }
+    }
+
+    #[test]
+    fn should_have_every_letter_of_the_alphabet_on_the_keyboard() {
+        for language in Language::all() {
+            for letter in &language.alphabet {
+                assert!(language.layout.iter().any(|row| row.contains(letter)), "'{}' is missing from the {} keyboard", letter, language.name);
+            }
+        }
+    }
+
+    #[test]
+    fn should_extend_alphabet_with_letters_of_custom_pack() {
+        let language = Language::for_pack(&WordPack::parse("team", "straße\nmüde"));
+        assert!(language.alphabet.contains(&'ß'));
+        assert!(language.alphabet.contains(&'ü'));
+        assert!(language.alphabet.contains(&'q'));
+    }
+
+    #[test]
+    fn should_fill_placeholders() {
+        let text = Messages::fill("{won} of {played}", &[("won", &3), ("played", &"5")]);
+        assert_eq!(text, "3 of 5");
+    }
+}
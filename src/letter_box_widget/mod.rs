@@ -1,50 +1,110 @@
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::prelude::{Color, Style, Widget};
+use ratatui::prelude::Widget;
 use ratatui::widgets::{Block, Paragraph};
 use crate::letters::LetterState;
 use ratatui::prelude::*;
+use crate::theme::Theme;
 
-pub struct LetterBoxWidget {
-    letter: char,
-    state: LetterState
+pub struct LetterBoxWidget<'a> {
+    label: String,
+    state: LetterState,
+    theme: &'a Theme,
+    highlighted: bool,
+    marked: bool
 }
 
-impl LetterBoxWidget {
-    pub fn new(letter: char, state: LetterState) -> Self {
-        Self { letter, state }
+impl <'a> LetterBoxWidget<'a> {
+    pub fn new(letter: char, state: LetterState, theme: &'a Theme) -> Self {
+        Self { label: letter.to_uppercase().to_string(), state, theme, highlighted: false, marked: false }
+    }
+
+    pub fn labelled(label: &str, state: LetterState, theme: &'a Theme) -> Self {
+        Self { label: label.to_string(), state, theme, highlighted: false, marked: false }
+    }
+
+    pub fn highlighted(mut self, highlighted: bool) -> Self {
+        self.highlighted = highlighted;
+        self
+    }
+
+    // Surrounds the letter with brackets telling its state without colour.
+    pub fn marked(mut self, marked: bool) -> Self {
+        self.marked = marked;
+        self
     }
 }
 
-impl Widget for LetterBoxWidget {
+impl <'a> Widget for LetterBoxWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) where Self: Sized {
 
-        let mut letter_block = Block::default();
-
-        letter_block = match self.state {
-            LetterState::RightPosition =>
-                letter_block.style(Style::default().bg(Color::Green)),
-            LetterState::WrongPosition =>
-                letter_block.style(Style::default().bg(Color::LightYellow).fg(Color::Black)),
-            LetterState::NotChecked =>
-                letter_block.style(Style::default().bg(Color::Gray).fg(Color::Black)),
-            LetterState::NotOccurring =>
-                letter_block.style(Style::default().bg(Color::DarkGray)),
+        let style = match self.highlighted {
+            true => self.theme.tile(&self.state).add_modifier(Modifier::REVERSED),
+            false => self.theme.tile(&self.state)
         };
+        let letter_block = Block::default().style(style);
+
+        // The letter sits on the middle row, so it also fits tiles a single row high.
+        let middle = Rect::new(area.x, area.y + area.height / 2, area.width, area.height.min(1));
 
-        let layout = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(vec![Constraint::Fill(1), Constraint::Fill(3), Constraint::Fill(1)])
-            .spacing(1)
-            .split(letter_block.inner(area));
+        // Tiles narrower than the brackets, and labels of wide keys, stay unmarked.
+        let label = match self.state {
+            _ if !self.marked || area.width < 3 || self.label.chars().count() != 1 => self.label,
+            LetterState::RightPosition => format!("[{}]", self.label),
+            LetterState::WrongPosition => format!("({})", self.label),
+            LetterState::NotOccurring => format!("-{}-", self.label),
+            LetterState::NotChecked => format!(" {} ", self.label)
+        };
 
-        let paragraph = Paragraph::new(self.letter.to_string().to_uppercase())
+        let paragraph = Paragraph::new(label)
             .centered()
             .bold();
 
-        paragraph.render(layout[1], buf);
+        paragraph.render(middle, buf);
 
         letter_block.render(area, buf);
 
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use ratatui::buffer::Buffer;
+    use ratatui::prelude::*;
+    use crate::letter_box_widget::LetterBoxWidget;
+    use crate::letters::LetterState;
+    use crate::snapshot::assert_snapshot;
+    use crate::theme::Theme;
+
+    #[test]
+    fn should_render_letter_in_the_middle_of_the_tile() {
+        let theme = Theme::dark();
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 17, 3));
+        LetterBoxWidget::new('a', LetterState::RightPosition, &theme).render(Rect::new(0, 0, 5, 3), &mut buffer);
+        LetterBoxWidget::new('b', LetterState::WrongPosition, &theme).render(Rect::new(6, 0, 5, 3), &mut buffer);
+        LetterBoxWidget::new('c', LetterState::NotOccurring, &theme).render(Rect::new(12, 0, 5, 3), &mut buffer);
+
+        assert_snapshot("letter_box_states", &buffer);
+    }
+
+    #[test]
+    fn should_mark_states_inside_wide_enough_tiles() {
+        let theme = Theme::dark();
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 15, 1));
+        LetterBoxWidget::new('a', LetterState::RightPosition, &theme).marked(true).render(Rect::new(0, 0, 3, 1), &mut buffer);
+        LetterBoxWidget::new('b', LetterState::WrongPosition, &theme).marked(true).render(Rect::new(4, 0, 3, 1), &mut buffer);
+        LetterBoxWidget::new('c', LetterState::NotOccurring, &theme).marked(true).render(Rect::new(8, 0, 3, 1), &mut buffer);
+        LetterBoxWidget::new('d', LetterState::RightPosition, &theme).marked(true).render(Rect::new(12, 0, 1, 1), &mut buffer);
+        LetterBoxWidget::labelled("OK", LetterState::RightPosition, &theme).marked(true).render(Rect::new(13, 0, 2, 1), &mut buffer);
+
+        assert_snapshot("letter_box_marked", &buffer);
+    }
+
+    #[test]
+    fn should_render_highlighted_label() {
+        let theme = Theme::dark();
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 7, 1));
+        LetterBoxWidget::labelled("ENTER", LetterState::NotChecked, &theme).highlighted(true).render(buffer.area, &mut buffer);
+
+        assert_snapshot("letter_box_highlighted_label", &buffer);
+    }
+}
@@ -1,19 +1,14 @@
 use std::collections::HashMap;
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub enum LetterState {
     RightPosition,
     WrongPosition,
+    #[default]
     NotChecked,
     NotOccurring
 }
 
-impl Default for LetterState {
-    fn default() -> Self {
-        LetterState::NotChecked
-    }
-}
-
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct GuessedLetter {
     pub value: char,
@@ -35,13 +30,22 @@ impl Default for GuessedLetter {
 
 #[derive(Clone)]
 pub struct Letters {
+    alphabet: Vec<char>,
     values: HashMap<char, LetterState>
 }
 
 impl Letters {
-    pub fn new() -> Self {
-        let values = ('a'..'z').into_iter().map(|c| (c, LetterState::NotChecked)).collect();
-        Self{ values }
+    pub fn new(alphabet: &[char]) -> Self {
+        let values = alphabet.iter().map(|c| (*c, LetterState::NotChecked)).collect();
+        Self{ alphabet: alphabet.to_vec(), values }
+    }
+
+    pub fn alphabet(&self) -> &[char] {
+        &self.alphabet
+    }
+
+    pub fn contains(&self, letter: char) -> bool {
+        self.values.contains_key(&letter)
     }
 
     pub fn state(&self, letter: char) -> LetterState {
@@ -1,72 +1,187 @@
 use ratatui::buffer::Buffer;
-use ratatui::layout::*;
 use ratatui::prelude::*;
+use crate::board_layout::Tiles;
+use crate::hit_areas::{HitAreas, Target};
 use crate::letter_box_widget::LetterBoxWidget;
-use crate::letters::Letters;
+use crate::letters::{LetterState, Letters};
+use crate::theme::Theme;
 
-#[derive(Clone)]
-pub struct LettersWidget {
+pub struct LettersWidget<'a> {
     letters: Letters,
-    layout: Vec<Vec<char>>
+    layout: Vec<Vec<char>>,
+    theme: &'a Theme,
+    tiles: Tiles,
+    pressed: Option<Target>,
+    enter_ready: bool,
+    marked: bool,
+    hit_areas: Option<&'a mut HitAreas>
 }
 
-impl LettersWidget {
-    pub fn new(letters: Letters) -> Self {
+const EXTRA_ROW_LEN: usize = 10;
+const ENTER: &str = "ENTER";
+const COMPACT_ENTER: &str = "⏎";
+const BACKSPACE: &str = "⌫";
 
-        let layout = vec![
-            vec!['q', 'w', 'e', 'r', 't', 'y', 'u', 'i', 'o', 'p'],
-            vec!['a', 's', 'd', 'f', 'g', 'h', 'j', 'k', 'l'],
-            vec!['z', 'x', 'c', 'v', 'b', 'n', 'm'],
-        ];
+impl <'a> LettersWidget<'a> {
+    pub fn new(letters: Letters, layout: &[Vec<char>], theme: &'a Theme) -> Self {
+
+        let layout = LettersWidget::fit(layout, letters.alphabet());
 
         Self{
             letters,
-            layout
+            layout,
+            theme,
+            tiles: Tiles::COMPACT,
+            pressed: None,
+            enter_ready: false,
+            marked: false,
+            hit_areas: None
         }
     }
-}
 
-impl  Widget for LettersWidget {
-    fn render(self, area: Rect, buf: &mut Buffer) where Self: Sized {
+    pub fn tiles(mut self, tiles: Tiles) -> Self {
+        self.tiles = tiles;
+        self
+    }
 
-        let letters_layout = &self.layout;
+    pub fn pressed(mut self, pressed: Option<Target>) -> Self {
+        self.pressed = pressed;
+        self
+    }
 
-        let max_len = letters_layout.iter().map(|l| l.len()).max().unwrap_or(0) as u32;
-        let max_height = (area.height / self.layout.len() as u16).max(2) - 1;
+    // Enter is shown in the colour of a right letter while the guess would be accepted.
+    pub fn enter_ready(mut self, enter_ready: bool) -> Self {
+        self.enter_ready = enter_ready;
+        self
+    }
 
-        let lines = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(letters_layout.iter().map(|_| Constraint::Length(max_height)).collect::<Vec<_>>())
-            .spacing(1)
-            .split(area);
+    pub fn marked(mut self, marked: bool) -> Self {
+        self.marked = marked;
+        self
+    }
 
-        for (line_idx, letters) in letters_layout.iter().enumerate() {
+    pub fn hit_areas(mut self, hit_areas: &'a mut HitAreas) -> Self {
+        self.hit_areas = Some(hit_areas);
+        self
+    }
 
-            let line = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints(vec![
-                    Constraint::Percentage(25),
-                    Constraint::Percentage(50),
-                    Constraint::Percentage(25)
-                ])
-                .split(lines[line_idx]);
+    pub fn rows(&self) -> usize {
+        self.layout.len()
+    }
 
-            let letter_boxes = Layout::default()
-                .direction(Direction::Horizontal)
-                .flex(Flex::Center)
-                .constraints(letters.iter().map(|_| Constraint::Ratio(1 ,max_len)).collect::<Vec<_>>())
-                .spacing(1)
-                .split(line[1]);
+    // Width of the widest row in tiles, counting the wide Enter and Backspace keys of the bottom row as two.
+    pub fn columns(&self) -> usize {
+        let last = self.layout.len().saturating_sub(1);
+        self.layout.iter().enumerate()
+            .map(|(i, row)| if i == last { row.len() + 4 } else { row.len() })
+            .max()
+            .unwrap_or(0)
+    }
 
-            for (letter_idx, letter) in letters.iter().enumerate() {
+    // Drops keys outside of the alphabet and adds rows with the letters the layout is missing.
+    fn fit(layout: &[Vec<char>], alphabet: &[char]) -> Vec<Vec<char>> {
+        let mut rows: Vec<Vec<char>> = layout.iter()
+            .map(|row| row.iter().filter(|c| alphabet.contains(c)).cloned().collect::<Vec<_>>())
+            .filter(|row| !row.is_empty())
+            .collect();
 
-                let state = self.letters.state(*letter);
+        let missing: Vec<char> = alphabet.iter()
+            .filter(|c| !layout.iter().any(|row| row.contains(c)))
+            .cloned()
+            .collect();
 
-                LetterBoxWidget::new(*letter, state)
-                    .render(letter_boxes[letter_idx], buf);
+        rows.extend(missing.chunks(EXTRA_ROW_LEN).map(|chunk| chunk.to_vec()));
+        rows
+    }
+}
 
+impl <'a> Widget for LettersWidget<'a> {
+    fn render(mut self, area: Rect, buf: &mut Buffer) where Self: Sized {
+
+        let last = self.layout.len().saturating_sub(1);
+        let wide = self.tiles.wide_width();
+        let enter = if wide as usize >= ENTER.len() + 2 { ENTER } else { COMPACT_ENTER };
+        let enter_state = if self.enter_ready { LetterState::RightPosition } else { LetterState::NotChecked };
+
+        for (line_idx, letters) in self.layout.iter().enumerate() {
+
+            // The bottom row is framed by Enter and Backspace, as on the keyboard of the original game.
+            let keys: Vec<Target> = if line_idx == last {
+                std::iter::once(Target::Enter)
+                    .chain(letters.iter().map(|letter| Target::Key(*letter)))
+                    .chain(std::iter::once(Target::Backspace))
+                    .collect()
+            } else {
+                letters.iter().map(|letter| Target::Key(*letter)).collect()
+            };
+
+            let widths: Vec<u16> = keys.iter().map(|key| match key {
+                Target::Key(_) => self.tiles.width,
+                _ => wide
+            }).collect();
+
+            let key_boxes = self.tiles.row_of(area, line_idx, &widths);
+
+            for (key, key_box) in keys.into_iter().zip(key_boxes) {
+
+                let widget = match key {
+                    Target::Key(letter) => LetterBoxWidget::new(letter, self.letters.state(letter), self.theme),
+                    Target::Enter => LetterBoxWidget::labelled(enter, enter_state.clone(), self.theme),
+                    _ => LetterBoxWidget::labelled(BACKSPACE, LetterState::NotChecked, self.theme)
+                };
+                widget.highlighted(self.pressed == Some(key)).marked(self.marked).render(key_box, buf);
+
+                if let Some(hit_areas) = self.hit_areas.as_mut() {
+                    hit_areas.add(key_box, key);
+                }
             }
         }
 
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::buffer::Buffer;
+    use ratatui::prelude::*;
+    use crate::board_layout::Tiles;
+    use crate::hit_areas::Target;
+    use crate::language::Language;
+    use crate::letters::{GuessedLetter, LetterState, Letters};
+    use crate::letters_widget::LettersWidget;
+    use crate::snapshot::assert_snapshot;
+    use crate::theme::Theme;
+
+    fn render(tiles: Tiles, pressed: Option<Target>, enter_ready: bool) -> Buffer {
+        let language = Language::english();
+        let theme = Theme::dark();
+        let mut letters = Letters::new(&language.alphabet);
+        letters.update_many(&vec![
+            GuessedLetter::new('t', LetterState::NotOccurring),
+            GuessedLetter::new('r', LetterState::RightPosition),
+            GuessedLetter::new('a', LetterState::RightPosition),
+            GuessedLetter::new('c', LetterState::WrongPosition),
+            GuessedLetter::new('e', LetterState::RightPosition)
+        ]);
+
+        let widget = LettersWidget::new(letters, &language.layout, &theme)
+            .tiles(tiles)
+            .pressed(pressed)
+            .enter_ready(enter_ready);
+        let area = Rect::new(0, 0, tiles.width(widget.columns()), tiles.height(widget.rows()));
+        let mut buffer = Buffer::empty(area);
+        widget.render(area, &mut buffer);
+        buffer
+    }
+
+    #[test]
+    fn should_render_compact_keyboard() {
+        assert_snapshot("letters_widget_compact", &render(Tiles::COMPACT, None, false));
+    }
+
+    #[test]
+    fn should_render_wide_keys_pressed_and_ready_to_submit() {
+        let tiles = Tiles { width: 3, height: 1, gap: 1, row_gap: 0 };
+        assert_snapshot("letters_widget_pressed", &render(tiles, Some(Target::Key('q')), true));
+    }
+}
@@ -0,0 +1,197 @@
+use std::io::{self, BufRead, Write};
+use crate::action::{Action, Response};
+use crate::game_manager::{GameManager, GameManagerState};
+use crate::game_state::{GameState, GameStatus};
+use crate::language::Messages;
+use crate::letters::LetterState;
+
+const QUIT: &str = "quit";
+const HINT: &str = "hint";
+
+// Plays with one line per guess and per result, for pipes, dumb terminals and
+// screen readers. Guesses go through the same actions as keys in the full screen game.
+pub fn run(manager: &mut GameManager, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    if !manager.can_continue() {
+        manager.restart();
+    }
+    let messages = manager.language().messages.clone();
+    let mut lines = input.lines();
+    let mut announced = false;
+
+    loop {
+        let state = match &manager.state {
+            GameManagerState::Playing(state) => state,
+            // An error rather than a message, so scripts see the game couldn't start.
+            GameManagerState::Failure(message) => return Err(io::Error::other(message.clone())),
+            GameManagerState::Loading => return Ok(())
+        };
+
+        if !announced {
+            introduce(state, &messages, &mut output)?;
+            announced = true;
+        }
+
+        write!(output, "{}", Messages::fill(messages.line_prompt, &[("guess", &(state.guess_count() + 1)), ("tries", &state.max_tries)]))?;
+        output.flush()?;
+
+        let Some(line) = lines.next().transpose()? else {
+            return writeln!(output);
+        };
+
+        match line.trim().to_lowercase().as_str() {
+            "" => continue,
+            QUIT => return Ok(()),
+            HINT => {
+                manager.perform(Action::Hint);
+                if let Some(toast) = manager.take_toast() {
+                    writeln!(output, "{}", toast.message)?;
+                }
+            },
+            word => guess(manager, word, &messages, &mut output)?
+        }
+
+        let GameManagerState::Playing(state) = &manager.state else {
+            continue;
+        };
+        if state.status == GameStatus::Pending {
+            continue;
+        }
+
+        let result = match state.status {
+            GameStatus::Won => Messages::fill(messages.line_won, &[("guesses", &state.guess_count()), ("tries", &state.max_tries)]),
+            _ => Messages::fill(messages.line_lost, &[("word", &state.target_word.to_uppercase())])
+        };
+        writeln!(output, "{}", result)?;
+
+        write!(output, "{}", messages.line_play_again)?;
+        output.flush()?;
+        match lines.next().transpose()? {
+            Some(answer) if answer.trim().to_lowercase() == messages.line_yes => {
+                manager.restart();
+                announced = false;
+            },
+            _ => return Ok(())
+        }
+    }
+}
+
+fn introduce(state: &GameState, messages: &Messages, output: &mut impl Write) -> io::Result<()> {
+    let intro = Messages::fill(messages.line_intro, &[
+        ("length", &state.word_len()),
+        ("tries", &state.max_tries),
+        ("hint", &HINT),
+        ("quit", &QUIT)
+    ]);
+    writeln!(output, "{}", intro)?;
+    for i in 0..state.guess_count() {
+        writeln!(output, "{}", describe(state, i, messages))?;
+    }
+    Ok(())
+}
+
+fn guess(manager: &mut GameManager, word: &str, messages: &Messages, output: &mut impl Write) -> io::Result<()> {
+    let GameManagerState::Playing(state) = &manager.state else {
+        return Ok(());
+    };
+
+    let length = word.chars().count();
+    if length != state.word_len() {
+        return writeln!(output, "{}", Messages::fill(messages.line_wrong_length, &[("length", &state.word_len()), ("count", &length)]));
+    }
+    if let Some(letter) = word.chars().find(|letter| !state.letters.contains(*letter)) {
+        return writeln!(output, "{}", Messages::fill(messages.line_not_a_letter, &[("letter", &letter)]));
+    }
+
+    if manager.enter_word(word) == Response::Rejected {
+        let message = manager.take_toast().map(|toast| toast.message).unwrap_or_default();
+        return writeln!(output, "{}", message);
+    }
+
+    match &manager.state {
+        GameManagerState::Playing(state) => writeln!(output, "{}", describe(state, state.guess_count() - 1, messages)),
+        _ => Ok(())
+    }
+}
+
+// Each letter with its colour, e.g. "T:grey R:green A:green C:yellow E:green".
+fn describe(state: &GameState, guess: usize, messages: &Messages) -> String {
+    state.get(guess).letters().iter()
+        .map(|letter| format!("{}:{}", letter.value.to_uppercase(), colour(&letter.state, messages)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn colour(state: &LetterState, messages: &Messages) -> &'static str {
+    match state {
+        LetterState::RightPosition => messages.green,
+        LetterState::WrongPosition => messages.yellow,
+        LetterState::NotOccurring => messages.grey,
+        LetterState::NotChecked => messages.blank
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Config;
+    use crate::game_manager::{GameManager, GameManagerState};
+    use crate::game_state::GameState;
+    use crate::history::History;
+    use crate::line_mode::run;
+    use crate::stats::Stats;
+
+    fn play(input: &str) -> (GameManager, String) {
+        play_in("en", "crane", "trace", input)
+    }
+
+    fn play_in(language: &str, answer: &str, guess: &str, input: &str) -> (GameManager, String) {
+        let mut manager = GameManager::new(History::in_memory(), Stats::in_memory());
+        manager.configure(&Config { language: Some(language.to_string()), words: Some(language.to_string()), ..Config::default() });
+        let mut state = GameState::new(answer, 6, &manager.language().alphabet);
+        state.set_dictionary(&[guess.to_string()]);
+        manager.state = GameManagerState::Playing(Box::new(state));
+
+        let mut output = Vec::new();
+        run(&mut manager, input.as_bytes(), &mut output).unwrap();
+        (manager, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn should_describe_each_guess_and_the_result() {
+        let (manager, output) = play("trace\ncrane\nn\n");
+
+        assert!(output.starts_with("Guess the 5-letter word in 6 tries."), "{}", output);
+        assert!(output.contains("Guess 1/6: T:grey R:green A:green C:yellow E:green\n"), "{}", output);
+        assert!(output.contains("Guess 2/6: C:green R:green A:green N:green E:green\nSolved in 2/6!\nPlay again? [y/N] "), "{}", output);
+        assert_eq!(manager.stats().last().map(|record| record.guesses), Some(2));
+    }
+
+    #[test]
+    fn should_explain_rejected_guesses_and_stop_at_end_of_input() {
+        let (manager, output) = play("cat\ncr4ne\nzzzzz\n");
+
+        assert!(output.contains("Expected a 5-letter word, got 3 letters."), "{}", output);
+        assert!(output.contains("'4' isn't a letter of this game."), "{}", output);
+        assert!(output.contains("Not in word list"), "{}", output);
+        assert!(output.ends_with("Guess 1/6: \n"), "{}", output);
+        assert!(manager.can_continue());
+    }
+
+    #[test]
+    fn should_fail_when_no_word_can_be_picked() {
+        let mut manager = GameManager::new(History::in_memory(), Stats::in_memory());
+        manager.configure(&Config { words: Some("en".to_string()), word_length: Some(10), ..Config::default() });
+
+        let error = run(&mut manager, "crane\n".as_bytes(), Vec::new()).unwrap_err();
+        assert_eq!(error.to_string(), "No 10-letter words in pack 'en'");
+    }
+
+    #[test]
+    fn should_speak_the_language_of_the_game() {
+        let (_, output) = play_in("pl", "kotek", "kotka", "kotka\nkotek\nt\n");
+
+        assert!(output.starts_with("Odgadnij 5-literowe słowo w 6 próbach."), "{}", output);
+        assert!(output.contains("Próba 1/6: K:zielona O:zielona T:zielona K:żółta A:szara\n"), "{}", output);
+        assert!(output.contains("Odgadnięte w 2/6!\nZagrać jeszcze raz? [t/N] "), "{}", output);
+        assert_eq!(output.matches("Odgadnij").count(), 2, "{}", output);
+    }
+}
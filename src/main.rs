@@ -1,116 +1,199 @@
 mod letters_widget;
 mod letters;
 mod event_handler;
+mod event_source;
 mod guess;
 mod game_state;
 mod guess_widget;
 mod game_manager;
 mod letter_box_widget;
-
-use letters_widget::LettersWidget;
-use std::io::{self, stdout};
-use event_handler::handle_events;
+mod paths;
+mod random;
+mod words;
+mod words_command;
+mod history;
+mod word_source;
+mod difficulty;
+mod stats;
+mod language;
+mod keyboard_layout;
+mod config;
+mod theme;
+mod key_bindings;
+mod settings;
+mod settings_widget;
+mod screen;
+mod animation;
+mod toast;
+mod board_layout;
+mod hit_areas;
+mod action;
+mod menu_widget;
+mod stats_widget;
+mod ui;
+mod saved_game;
+mod solver;
+mod cli;
+mod line_mode;
+mod protocol;
+mod bench;
+mod decision_tree;
+#[cfg(test)]
+mod snapshot;
+
+use std::backtrace::Backtrace;
+use std::env;
+use std::io::{self, stdout, IsTerminal};
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use event_source::CrosstermEvents;
 
 use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
-use ratatui::{prelude::*};
-use ratatui::widgets::{Block, Borders, BorderType, Padding, Paragraph};
-use crate::game_manager::{GameManager, GameManagerState};
-use crate::game_state::GameStatus;
-use crate::guess_widget::GuessWidget;
+use ratatui::prelude::*;
+use crate::cli::{Command, PlayOptions};
+use crate::game_manager::GameManager;
+use crate::config::Config;
+use crate::history::History;
+use crate::saved_game::SavedGame;
+use crate::screen::Screen;
+use crate::stats::Stats;
+use crate::ui::ui;
+
+fn exit_with(result: Result<(), String>) -> ! {
+    match result {
+        Ok(()) => process::exit(0),
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(1)
+        }
+    }
+}
+
+// Also returns the problems found in the config, for the front end to show.
+fn game_manager(options: &PlayOptions) -> (GameManager, Vec<String>) {
+    let (mut config, mut errors) = match Config::load() {
+        Ok(config) => (config, Vec::new()),
+        Err(error) => (Config::default(), vec![error])
+    };
+    options.apply(&mut config);
+
+    // Games played by programs stay out of the player's history, stats and saved game.
+    let bot = options.protocol.is_some();
+    let mut manager = if bot {
+        GameManager::new(History::in_memory(), Stats::in_memory())
+    } else {
+        GameManager::new(History::load(), Stats::load())
+    };
+    errors.extend(manager.configure(&config));
+    if let Some(seed) = options.seed {
+        if let Err(error) = manager.seed(seed) {
+            exit_with(Err(error));
+        }
+    }
+    // A seeded run starts from its own first word, the saved game waits for the next normal one.
+    if !bot && !manager.seeded() {
+        if let Some(game) = SavedGame::take() {
+            manager.resume(game);
+        }
+    }
+    (manager, errors)
+}
 
 fn main() -> io::Result<()> {
-    enable_raw_mode()?;
-    stdout().execute(EnterAlternateScreen)?;
-    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let command = cli::parse(&args).unwrap_or_else(|message| exit_with(Err(message)));
+
+    let options = match command {
+        Command::Play(options) => options,
+        Command::Stats => exit_with(cli::stats(&Stats::load())),
+        Command::Share => exit_with(cli::share(&Stats::load())),
+        Command::Solve(options) => exit_with(cli::solve(&options)),
+        Command::Words(args) => exit_with(words_command::run(&args)),
+        Command::Bench(options) => exit_with(bench::run(&options)),
+        Command::Version => {
+            println!("{}", cli::version());
+            exit_with(Ok(()))
+        },
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            exit_with(Ok(()))
+        }
+    };
+    let (mut manager, errors) = game_manager(&options);
 
-    let mut should_quit = false;
+    if options.protocol.is_some() {
+        for error in &errors {
+            eprintln!("{}", error);
+        }
+        return protocol::run(&mut manager, io::stdin().lock(), stdout().lock());
+    }
 
-    let mut manager = GameManager::new();
-    manager.restart();
+    let result = if options.plain || !io::stdin().is_terminal() || !stdout().is_terminal() {
+        for error in &errors {
+            eprintln!("{}", error);
+        }
+        line_mode::run(&mut manager, io::stdin().lock(), stdout().lock())
+    } else {
+        manager.open_menu();
+        if !errors.is_empty() {
+            manager.push_screen(Screen::InvalidConfig(errors));
+        }
+        full_screen(&mut manager)
+    };
 
-    while !should_quit {
-        terminal.draw(|frame| ui(&mut manager, frame))?;
-        should_quit = handle_events(&mut manager)?;
-    }
+    save_game(&manager);
+    exit_with(result.map_err(|error| error.to_string()))
+}
 
-    disable_raw_mode()?;
-    stdout().execute(LeaveAlternateScreen)?;
-    Ok(())
+fn full_screen(manager: &mut GameManager) -> io::Result<()> {
+    install_panic_hook();
+
+    // Setting up runs inside too, so a failure halfway still restores the terminal below.
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        enable_raw_mode()?;
+        stdout().execute(EnterAlternateScreen)?;
+        stdout().execute(EnableMouseCapture)?;
+        let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+        let mut events = CrosstermEvents::new()?;
+
+        event_handler::run(manager, &mut events, |manager| {
+            terminal.draw(|frame| ui(manager, frame))?;
+            Ok(())
+        })
+    }));
+
+    restore_terminal();
+    result.unwrap_or_else(|_| {
+        // The hook has already reported the panic.
+        save_game(manager);
+        process::exit(101)
+    })
 }
 
-fn ui(manager: &mut GameManager, frame: &mut Frame) {
-    match manager.state {
-        GameManagerState::Playing(ref mut state) => {
-            let status = state.status.clone();
-            let target_word = state.target_word.clone();
-            let letters_widget = LettersWidget::new(state.letters.clone());
-            let guess_widget = GuessWidget::new(state);
-
-            let (title, title_style) = match status {
-                GameStatus::Won =>
-                    ("You won! Press enter to start over.".to_string(), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
-                GameStatus::Lost =>
-                    (format!("You lost! The correct word was '{}'. Press enter to start over.", target_word), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
-                _ =>
-                    ("Type and press enter to guess. Backspace to clear. Esc to quit.".to_string(), Style::default().fg(Color::White))
-            };
-
-            let bottom_block = Block::default()
-                .title(title)
-                .title_alignment(Alignment::Center)
-                .title_style(title_style)
-                .borders(Borders::TOP)
-                .padding(Padding::new(1, 1, 1, 1))
-                .border_type(BorderType::Double);
-
-            let top_block = Block::default()
-                .padding(Padding::new(1, 1, 1, 0));
-
-            let layout = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints(vec![
-                    Constraint::Percentage(65),
-                    Constraint::Percentage(35),
-                ])
-                .spacing(2)
-                .split(frame.size());
-
-            guess_widget.render(top_block.inner(layout[0]), frame.buffer_mut());
-
-            frame.render_widget(
-                top_block,
-                layout[0]
-            );
-
-            letters_widget.render(bottom_block.inner(layout[1]), frame.buffer_mut());
-
-            frame.render_widget(
-                bottom_block,
-                layout[1]
-            );
-        },
-        GameManagerState::Failure(ref message) =>
-            frame.render_widget(
-                Paragraph::new(format!("Error: {}. Press enter to retry. Esc to quit.", message))
-                    .centered()
-                    .style(Style::default().fg(Color::Red))
-                    .bold(),
-                frame.size()
-            ),
-        GameManagerState::Loading => {
-            frame.render_widget(
-                Paragraph::new("Loading...")
-                    .centered()
-                    .style(Style::default().fg(Color::LightBlue))
-                    .bold(),
-                frame.size()
-            )
+fn save_game(manager: &GameManager) {
+    if let Some(game) = manager.saved_game() {
+        if let Err(error) = game.save() {
+            eprintln!("Couldn't save the game: {}", error);
         }
     }
+}
 
+// Safe to call more than once, and when the terminal was never set up.
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = stdout().execute(DisableMouseCapture);
+    let _ = stdout().execute(LeaveAlternateScreen);
+}
 
-
-}
\ No newline at end of file
+// Leaves the alternate screen before printing, or the message would be lost with it.
+fn install_panic_hook() {
+    panic::set_hook(Box::new(|info| {
+        restore_terminal();
+        eprintln!("{}\n\n{}", info, Backtrace::force_capture());
+    }));
+}
@@ -0,0 +1,63 @@
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, BorderType, Padding, Paragraph};
+use crate::hit_areas::{HitAreas, Target};
+use crate::theme::Theme;
+
+// A titled list of entries with one of them selected, used by the main menu and the mode picker.
+pub struct MenuWidget<'a> {
+    title: &'a str,
+    entries: Vec<String>,
+    selected: usize,
+    help: String,
+    theme: &'a Theme,
+    hit_areas: Option<&'a mut HitAreas>
+}
+
+impl <'a> MenuWidget<'a> {
+    pub fn new(title: &'a str, entries: Vec<String>, selected: usize, help: String, theme: &'a Theme) -> Self {
+        Self { title, entries, selected, help, theme, hit_areas: None }
+    }
+
+    pub fn hit_areas(mut self, hit_areas: &'a mut HitAreas) -> Self {
+        self.hit_areas = Some(hit_areas);
+        self
+    }
+}
+
+impl <'a> Widget for MenuWidget<'a> {
+    fn render(mut self, area: Rect, buf: &mut Buffer) where Self: Sized {
+
+        let block = Block::default()
+            .title(self.title)
+            .title_alignment(Alignment::Center)
+            .title_style(self.theme.title.add_modifier(Modifier::BOLD))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .padding(Padding::new(2, 2, 1, 1));
+
+        let entries = self.entries.len();
+        let mut lines: Vec<Line> = self.entries.into_iter().enumerate().map(|(i, entry)| {
+            if i == self.selected {
+                Line::styled(entry, self.theme.title.add_modifier(Modifier::REVERSED))
+            } else {
+                Line::styled(entry, self.theme.title)
+            }
+        }).collect();
+
+        lines.push(Line::default());
+        lines.push(Line::styled(self.help, self.theme.title));
+
+        // Each entry is on its own line at the top of the block.
+        let inner = block.inner(area);
+        if let Some(hit_areas) = self.hit_areas.as_mut() {
+            for i in 0..entries {
+                hit_areas.add(Rect::new(inner.x, inner.y + i as u16, inner.width, 1).intersection(inner), Target::Entry(i));
+            }
+        }
+
+        Paragraph::new(lines)
+            .centered()
+            .block(block)
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,31 @@
+use std::env;
+use std::path::PathBuf;
+
+const APP_DIR: &str = "wordle";
+
+fn xdg_dir(variable: &str, fallback: &str) -> Option<PathBuf> {
+    match env::var_os(variable) {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir).join(APP_DIR)),
+        _ => env::var_os("HOME").map(|home| PathBuf::from(home).join(fallback).join(APP_DIR))
+    }
+}
+
+pub fn data_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_DATA_HOME", ".local/share")
+}
+
+pub fn packs_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("packs"))
+}
+
+pub fn config_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME", ".config")
+}
+
+pub fn cache_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CACHE_HOME", ".cache")
+}
+
+pub fn trees_dir() -> Option<PathBuf> {
+    cache_dir().map(|dir| dir.join("trees"))
+}
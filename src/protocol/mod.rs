@@ -0,0 +1,174 @@
+use std::io::{self, BufRead, Write};
+use serde::{Deserialize, Serialize};
+use crate::action::Response;
+use crate::game_manager::{GameManager, GameManagerState};
+use crate::game_state::{GameState, GameStatus};
+use crate::letters::LetterState;
+
+pub const PROTOCOLS: [&str; 1] = ["jsonl"];
+
+// Everything the game writes, one JSON object per line.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Event {
+    NewGame { length: usize, tries: u8 },
+    Feedback { guess: String, feedback: Vec<&'static str>, remaining: usize },
+    Error { code: &'static str, message: String },
+    Result { won: bool, guesses: usize, answer: String }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Request {
+    guess: String
+}
+
+// Lets a bot play game after game: every line read is a guess, and a finished
+// game is followed by the next one until the input ends.
+pub fn run(manager: &mut GameManager, mut input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    loop {
+        if !manager.can_continue() {
+            manager.restart();
+        }
+        match &manager.state {
+            GameManagerState::Playing(_) => {},
+            GameManagerState::Failure(message) => {
+                return emit(&mut output, &Event::Error { code: "no_game", message: message.clone() });
+            },
+            GameManagerState::Loading => return Ok(())
+        }
+
+        if !referee(manager, &mut input, &mut output)? {
+            return Ok(());
+        }
+    }
+}
+
+// Plays the game in progress to its end, or until the input ends, which is when
+// it returns false.
+pub fn referee(manager: &mut GameManager, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<bool> {
+    let GameManagerState::Playing(state) = &manager.state else {
+        return Ok(false);
+    };
+    emit(output, &Event::NewGame { length: state.word_len(), tries: state.max_tries })?;
+
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(false);
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let event = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => guess(manager, &request.guess.to_lowercase()),
+            Err(error) => Event::Error { code: "invalid_request", message: error.to_string() }
+        };
+        emit(output, &event)?;
+
+        match &manager.state {
+            GameManagerState::Playing(state) if state.status == GameStatus::Pending => {},
+            GameManagerState::Playing(state) => {
+                emit(output, &Event::Result {
+                    won: state.status == GameStatus::Won,
+                    guesses: state.guess_count(),
+                    answer: state.target_word.clone()
+                })?;
+                return Ok(true);
+            },
+            _ => return Ok(false)
+        }
+    }
+}
+
+fn guess(manager: &mut GameManager, word: &str) -> Event {
+    let GameManagerState::Playing(state) = &manager.state else {
+        return Event::Error { code: "no_game", message: "No game in progress".to_string() };
+    };
+
+    let length = word.chars().count();
+    if length != state.word_len() {
+        return Event::Error { code: "wrong_length", message: format!("Expected {} letters, got {}", state.word_len(), length) };
+    }
+    if let Some(letter) = word.chars().find(|letter| !state.letters.contains(*letter)) {
+        return Event::Error { code: "invalid_letter", message: format!("'{}' isn't a letter of this game", letter) };
+    }
+
+    if manager.enter_word(word) == Response::Rejected {
+        let message = manager.take_toast().map(|toast| toast.message).unwrap_or_default();
+        return Event::Error { code: "rejected", message };
+    }
+
+    match &manager.state {
+        GameManagerState::Playing(state) => feedback(state),
+        _ => Event::Error { code: "no_game", message: "No game in progress".to_string() }
+    }
+}
+
+fn feedback(state: &GameState) -> Event {
+    let letters = state.get(state.guess_count() - 1).letters().clone();
+    Event::Feedback {
+        guess: letters.iter().map(|letter| letter.value).collect(),
+        feedback: letters.iter().map(|letter| match letter.state {
+            LetterState::RightPosition => "correct",
+            LetterState::WrongPosition => "present",
+            LetterState::NotOccurring | LetterState::NotChecked => "absent"
+        }).collect(),
+        remaining: state.max_tries as usize - state.guess_count()
+    }
+}
+
+fn emit(output: &mut impl Write, event: &Event) -> io::Result<()> {
+    serde_json::to_writer(&mut *output, event)?;
+    writeln!(output)?;
+    output.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Config;
+    use crate::game_manager::{GameManager, GameManagerState};
+    use crate::game_state::GameState;
+    use crate::history::History;
+    use crate::protocol::run;
+    use crate::stats::Stats;
+
+    fn play(input: &str) -> Vec<serde_json::Value> {
+        let mut manager = GameManager::new(History::in_memory(), Stats::in_memory());
+        manager.configure(&Config { words: Some("en".to_string()), ..Config::default() });
+        let mut state = GameState::new("crane", 6, &manager.language().alphabet);
+        state.set_dictionary(&["trace".to_string()]);
+        manager.state = GameManagerState::Playing(Box::new(state));
+
+        let mut output = Vec::new();
+        run(&mut manager, input.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap().lines().map(|line| serde_json::from_str(line).unwrap()).collect()
+    }
+
+    #[test]
+    fn should_answer_guesses_and_start_the_next_game() {
+        let events = play("{\"guess\":\"trace\"}\n\n{\"guess\":\"CRANE\"}\n");
+
+        assert_eq!(events[0], serde_json::json!({"type": "new_game", "length": 5, "tries": 6}));
+        assert_eq!(events[1], serde_json::json!({
+            "type": "feedback",
+            "guess": "trace",
+            "feedback": ["absent", "correct", "correct", "present", "correct"],
+            "remaining": 5
+        }));
+        assert_eq!(events[3], serde_json::json!({"type": "result", "won": true, "guesses": 2, "answer": "crane"}));
+        assert_eq!(events[4]["type"], "new_game");
+        assert_eq!(events.len(), 5);
+    }
+
+    #[test]
+    fn should_report_errors_without_using_up_tries() {
+        let events = play("not json\n{\"guess\":\"cat\"}\n{\"guess\":\"cr4ne\"}\n{\"guess\":\"zzzzz\"}\n{\"word\":\"crane\"}\n");
+        let codes: Vec<&str> = events[1..].iter().map(|event| event["code"].as_str().unwrap_or("")).collect();
+
+        assert_eq!(codes, vec!["invalid_request", "wrong_length", "invalid_letter", "rejected", "invalid_request"]);
+        assert_eq!(events[4]["message"], "Not in word list");
+    }
+}
@@ -0,0 +1,96 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use crate::difficulty::Difficulty;
+use crate::paths;
+
+const SAVED_GAME_FILE: &str = "game.txt";
+
+// An unfinished game, kept between runs so quitting, a signal or a crash
+// doesn't lose it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SavedGame {
+    pub difficulty: Difficulty,
+    pub hard_mode: bool,
+    pub max_tries: u8,
+    pub target_word: String,
+    pub guesses: Vec<String>
+}
+
+impl SavedGame {
+    fn path() -> Option<PathBuf> {
+        paths::data_dir().map(|dir| dir.join(SAVED_GAME_FILE))
+    }
+
+    fn parse(contents: &str) -> Option<Self> {
+        let mut fields = contents.split_whitespace();
+        let difficulty = fields.next()?.parse().ok()?;
+        let hard_mode = match fields.next()? {
+            "hard" => true,
+            "normal" => false,
+            _ => return None
+        };
+        let max_tries = fields.next()?.parse().ok()?;
+        let target_word = fields.next()?.to_string();
+        let guesses = fields.map(str::to_string).collect();
+        Some(Self { difficulty, hard_mode, max_tries, target_word, guesses })
+    }
+
+    fn format(&self) -> String {
+        let mut fields = vec![
+            self.difficulty.to_string(),
+            (if self.hard_mode { "hard" } else { "normal" }).to_string(),
+            self.max_tries.to_string(),
+            self.target_word.clone()
+        ];
+        fields.extend(self.guesses.iter().cloned());
+        fields.join(" ")
+    }
+
+    // Loads the saved game and removes it, so it's only resumed once.
+    pub fn take() -> Option<Self> {
+        let path = Self::path()?;
+        let contents = fs::read_to_string(&path).ok()?;
+        let _ = fs::remove_file(&path);
+        Self::parse(&contents)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        match Self::path() {
+            Some(path) => {
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                fs::write(path, self.format() + "\n")
+            },
+            None => Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::difficulty::Difficulty;
+    use crate::saved_game::SavedGame;
+
+    #[test]
+    fn should_round_trip_through_text() {
+        let game = SavedGame {
+            difficulty: Difficulty::Hard,
+            hard_mode: true,
+            max_tries: 6,
+            target_word: "crane".to_string(),
+            guesses: vec!["trace".to_string(), "cable".to_string()]
+        };
+
+        assert_eq!(game.format(), "hard hard 6 crane trace cable");
+        assert_eq!(SavedGame::parse(&game.format()), Some(game));
+    }
+
+    #[test]
+    fn should_ignore_damaged_files() {
+        assert_eq!(SavedGame::parse(""), None);
+        assert_eq!(SavedGame::parse("hard maybe 6 crane"), None);
+        assert_eq!(SavedGame::parse("normal normal six crane"), None);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::difficulty::Difficulty;
+use crate::language::Messages;
+use crate::settings::{SettingsState, WORD_LENGTHS};
+
+pub enum Screen {
+    MainMenu(Menu),
+    NewGame(NewGameState),
+    Statistics,
+    Settings(Box<SettingsState>),
+    Help,
+    About,
+    InvalidConfig(Vec<String>)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuItem {
+    Continue,
+    NewGame,
+    Statistics,
+    Settings,
+    Help,
+    About,
+    Quit
+}
+
+impl MenuItem {
+    pub fn label(&self, messages: &Messages) -> &'static str {
+        match self {
+            MenuItem::Continue => messages.menu_continue,
+            MenuItem::NewGame => messages.menu_new_game,
+            MenuItem::Statistics => messages.menu_statistics,
+            MenuItem::Settings => messages.menu_settings,
+            MenuItem::Help => messages.menu_help_item,
+            MenuItem::About => messages.menu_about,
+            MenuItem::Quit => messages.menu_quit
+        }
+    }
+}
+
+pub struct Menu {
+    pub items: Vec<MenuItem>,
+    pub selected: usize
+}
+
+impl Menu {
+    // Continue is only offered while there's a game to go back to.
+    pub fn new(can_continue: bool) -> Self {
+        let items = [
+            MenuItem::Continue,
+            MenuItem::NewGame,
+            MenuItem::Statistics,
+            MenuItem::Settings,
+            MenuItem::Help,
+            MenuItem::About,
+            MenuItem::Quit
+        ].into_iter().filter(|item| can_continue || *item != MenuItem::Continue).collect();
+
+        Self { items, selected: 0 }
+    }
+
+    pub fn selected(&self) -> MenuItem {
+        self.items[self.selected]
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % self.items.len();
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = (self.selected + self.items.len() - 1) % self.items.len();
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameOption {
+    Difficulty,
+    HardMode,
+    WordLength
+}
+
+impl GameOption {
+    pub const ALL: [GameOption; 3] = [GameOption::Difficulty, GameOption::HardMode, GameOption::WordLength];
+
+    pub fn label(&self, messages: &Messages) -> &'static str {
+        match self {
+            GameOption::Difficulty => messages.difficulty_label,
+            GameOption::HardMode => messages.hard_mode,
+            GameOption::WordLength => messages.word_length
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NewGameState {
+    pub difficulty: Difficulty,
+    pub hard_mode: bool,
+    pub word_length: u8,
+    pub selected: usize
+}
+
+impl NewGameState {
+
+    pub fn new(difficulty: Difficulty, hard_mode: bool, word_length: u8) -> Self {
+        Self { difficulty, hard_mode, word_length, selected: 0 }
+    }
+
+    pub fn value(&self, option: GameOption, messages: &Messages) -> String {
+        match option {
+            GameOption::Difficulty => messages.difficulty(self.difficulty).to_string(),
+            GameOption::HardMode => if self.hard_mode { messages.on } else { messages.off }.to_string(),
+            GameOption::WordLength => self.word_length.to_string()
+        }
+    }
+
+    pub fn selected(&self) -> GameOption {
+        GameOption::ALL[self.selected]
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % GameOption::ALL.len();
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = (self.selected + GameOption::ALL.len() - 1) % GameOption::ALL.len();
+    }
+
+    pub fn change(&mut self, forward: bool) {
+        match self.selected() {
+            GameOption::Difficulty => {
+                let len = Difficulty::ALL.len();
+                let i = Difficulty::ALL.iter().position(|d| *d == self.difficulty).unwrap_or(0);
+                self.difficulty = Difficulty::ALL[if forward { (i + 1) % len } else { (i + len - 1) % len }];
+            },
+            GameOption::HardMode => self.hard_mode = !self.hard_mode,
+            GameOption::WordLength => {
+                self.word_length = match forward {
+                    true if self.word_length < *WORD_LENGTHS.end() => self.word_length + 1,
+                    true => *WORD_LENGTHS.start(),
+                    false if self.word_length > *WORD_LENGTHS.start() => self.word_length - 1,
+                    false => *WORD_LENGTHS.end()
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::difficulty::Difficulty;
+    use crate::screen::{Menu, MenuItem, NewGameState};
+
+    #[test]
+    fn should_only_offer_continue_with_game_in_progress() {
+        assert_eq!(Menu::new(true).selected(), MenuItem::Continue);
+        assert_eq!(Menu::new(false).selected(), MenuItem::NewGame);
+    }
+
+    #[test]
+    fn should_wrap_menu_selection() {
+        let mut menu = Menu::new(false);
+        menu.select_previous();
+        assert_eq!(menu.selected(), MenuItem::Quit);
+    }
+
+    #[test]
+    fn should_pick_game_mode() {
+        let mut new_game = NewGameState::new(Difficulty::Normal, false, 10);
+        new_game.change(true);
+        new_game.select_next();
+        new_game.change(true);
+        new_game.select_next();
+        new_game.change(true);
+        assert_eq!(new_game, NewGameState { difficulty: Difficulty::Hard, hard_mode: true, word_length: 3, selected: 2 });
+    }
+}
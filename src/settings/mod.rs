@@ -0,0 +1,231 @@
+use crate::config::Config;
+use crate::difficulty::Difficulty;
+use crate::keyboard_layout::KeyboardLayout;
+use crate::language::{Language, Messages};
+use crate::theme::Theme;
+use crate::words::WordPack;
+
+pub const DEFAULT_WORD_LENGTH: u8 = 5;
+pub const DEFAULT_TRIES: u8 = 6;
+pub const WORD_LENGTHS: std::ops::RangeInclusive<u8> = 3..=10;
+pub const TRIES: std::ops::RangeInclusive<u8> = 3..=10;
+const DEFAULT: &str = "default";
+const API: &str = "api";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Setting {
+    WordLength,
+    Tries,
+    HardMode,
+    Difficulty,
+    Language,
+    Words,
+    Theme,
+    Keyboard,
+    Animations,
+    Bell,
+    Accessible
+}
+
+impl Setting {
+
+    pub const ALL: [Setting; 11] = [
+        Setting::WordLength,
+        Setting::Tries,
+        Setting::HardMode,
+        Setting::Difficulty,
+        Setting::Language,
+        Setting::Words,
+        Setting::Theme,
+        Setting::Keyboard,
+        Setting::Animations,
+        Setting::Bell,
+        Setting::Accessible
+    ];
+
+    pub fn label(&self, messages: &Messages) -> &'static str {
+        match self {
+            Setting::WordLength => messages.word_length,
+            Setting::Tries => messages.tries,
+            Setting::HardMode => messages.hard_mode,
+            Setting::Difficulty => messages.difficulty_label,
+            Setting::Language => messages.language,
+            Setting::Words => messages.words,
+            Setting::Theme => messages.theme,
+            Setting::Keyboard => messages.keyboard,
+            Setting::Animations => messages.animations,
+            Setting::Bell => messages.bell,
+            Setting::Accessible => messages.accessible
+        }
+    }
+
+    pub fn value(&self, config: &Config, messages: &Messages) -> String {
+        match self {
+            Setting::WordLength => config.word_length.unwrap_or(DEFAULT_WORD_LENGTH).to_string(),
+            Setting::Tries => config.tries.unwrap_or(DEFAULT_TRIES).to_string(),
+            Setting::HardMode => if config.hard_mode.unwrap_or(false) { messages.on } else { messages.off }.to_string(),
+            // Without a difficulty answers come from the whole word list.
+            Setting::Difficulty => match config.difficulty.as_deref() {
+                Some(difficulty) => difficulty.parse().map(|difficulty| messages.difficulty(difficulty)).unwrap_or(difficulty),
+                None => messages.whole_list
+            }.to_string(),
+            Setting::Language => config.language.clone().unwrap_or(Language::english().code),
+            Setting::Words => config.words.clone().unwrap_or(messages.default.to_string()),
+            Setting::Theme => config.theme.clone().unwrap_or(Theme::dark().name),
+            Setting::Keyboard => config.keyboard.clone().unwrap_or(messages.default.to_string()),
+            Setting::Animations => if config.animations.unwrap_or(true) { messages.on } else { messages.off }.to_string(),
+            Setting::Bell => if config.bell.unwrap_or(false) { messages.on } else { messages.off }.to_string(),
+            Setting::Accessible => if config.accessible.unwrap_or(false) { messages.on } else { messages.off }.to_string()
+        }
+    }
+
+    pub fn change(&self, config: &mut Config, forward: bool) {
+        match self {
+            Setting::WordLength => {
+                let values: Vec<String> = WORD_LENGTHS.map(|n| n.to_string()).collect();
+                let current = config.word_length.unwrap_or(DEFAULT_WORD_LENGTH).to_string();
+                config.word_length = cycle(&values, &current, forward).parse().ok();
+            },
+            Setting::Tries => {
+                let values: Vec<String> = TRIES.map(|n| n.to_string()).collect();
+                let current = config.tries.unwrap_or(DEFAULT_TRIES).to_string();
+                config.tries = cycle(&values, &current, forward).parse().ok();
+            },
+            Setting::HardMode => config.hard_mode = Some(!config.hard_mode.unwrap_or(false)),
+            Setting::Difficulty => {
+                let mut values = vec![DEFAULT.to_string()];
+                values.extend(Difficulty::ALL.iter().map(|d| d.to_string()));
+                let current = config.difficulty.clone().unwrap_or(DEFAULT.to_string());
+                config.difficulty = Some(cycle(&values, &current, forward)).filter(|difficulty| difficulty != DEFAULT);
+            },
+            Setting::Language => {
+                let values: Vec<String> = Language::all().into_iter().map(|language| language.code).collect();
+                let current = config.language.clone().unwrap_or(Language::english().code);
+                config.language = Some(cycle(&values, &current, forward));
+            },
+            Setting::Words => {
+                let mut values = vec![DEFAULT.to_string(), API.to_string()];
+                values.extend(WordPack::all_embedded().iter().map(|pack| pack.name().to_string()));
+                values.extend(WordPack::user_packs().unwrap_or_default().iter().map(|pack| pack.name().to_string()));
+                if let Some(words) = &config.words {
+                    if !values.contains(words) {
+                        values.push(words.clone());
+                    }
+                }
+                let current = config.words.clone().unwrap_or(DEFAULT.to_string());
+                config.words = Some(cycle(&values, &current, forward)).filter(|words| words != DEFAULT);
+            },
+            Setting::Theme => {
+                let values: Vec<String> = Theme::all(&config.themes)
+                    .unwrap_or_else(|_| Theme::built_in())
+                    .into_iter()
+                    .map(|theme| theme.name)
+                    .collect();
+                let current = config.theme.clone().unwrap_or(Theme::dark().name);
+                config.theme = Some(cycle(&values, &current, forward));
+            },
+            Setting::Keyboard => {
+                let mut values = vec![DEFAULT.to_string()];
+                values.extend(KeyboardLayout::names(&config.layouts));
+                let current = config.keyboard.clone().unwrap_or(DEFAULT.to_string());
+                config.keyboard = Some(cycle(&values, &current, forward)).filter(|keyboard| keyboard != DEFAULT);
+            },
+            Setting::Animations => config.animations = Some(!config.animations.unwrap_or(true)),
+            Setting::Bell => config.bell = Some(!config.bell.unwrap_or(false)),
+            Setting::Accessible => config.accessible = Some(!config.accessible.unwrap_or(false))
+        }
+    }
+}
+
+fn cycle(values: &[String], current: &str, forward: bool) -> String {
+    let len = values.len();
+    let next = match values.iter().position(|value| value == current) {
+        Some(i) if forward => (i + 1) % len,
+        Some(i) => (i + len - 1) % len,
+        None => 0
+    };
+    values[next].clone()
+}
+
+pub struct SettingsState {
+    pub config: Config,
+    pub selected: usize,
+    pub error: Option<String>,
+    // The config as it was opened, to tell which settings were edited.
+    opened: Config
+}
+
+impl SettingsState {
+
+    pub fn new(config: Config, error: Option<String>) -> Self {
+        Self { opened: config.clone(), config, selected: 0, error }
+    }
+
+    // Whether the edits change which words are played, so the game in progress no longer fits them.
+    pub fn changes_game(&self) -> bool {
+        let (opened, config) = (&self.opened, &self.config);
+        opened.word_length != config.word_length
+            || opened.tries != config.tries
+            || opened.words != config.words
+            || opened.difficulty != config.difficulty
+            || opened.language != config.language
+    }
+
+    pub fn selected(&self) -> Setting {
+        Setting::ALL[self.selected]
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % Setting::ALL.len();
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = (self.selected + Setting::ALL.len() - 1) % Setting::ALL.len();
+    }
+
+    pub fn change(&mut self, forward: bool) {
+        self.selected().change(&mut self.config, forward);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Config;
+    use crate::settings::{Setting, SettingsState};
+
+    #[test]
+    fn should_cycle_through_values() {
+        let mut config = Config::default();
+        Setting::WordLength.change(&mut config, true);
+        assert_eq!(config.word_length, Some(6));
+        Setting::Tries.change(&mut config, false);
+        assert_eq!(config.tries, Some(5));
+        Setting::HardMode.change(&mut config, true);
+        assert_eq!(config.hard_mode, Some(true));
+    }
+
+    #[test]
+    fn should_wrap_around_and_return_to_default() {
+        let mut config = Config::default();
+        Setting::Keyboard.change(&mut config, false);
+        assert_eq!(config.keyboard.as_deref(), Some("alphabetical"));
+        Setting::Keyboard.change(&mut config, true);
+        assert_eq!(config.keyboard, None);
+
+        Setting::Difficulty.change(&mut config, true);
+        assert_eq!(config.difficulty.as_deref(), Some("easy"));
+        Setting::Difficulty.change(&mut config, false);
+        assert_eq!(config.difficulty, None);
+    }
+
+    #[test]
+    fn should_tell_edits_changing_the_game_from_cosmetic_ones() {
+        let mut settings = SettingsState::new(Config::default(), None);
+        Setting::Theme.change(&mut settings.config, true);
+        Setting::Bell.change(&mut settings.config, true);
+        assert!(!settings.changes_game());
+
+        Setting::Tries.change(&mut settings.config, true);
+        assert!(settings.changes_game());
+    }
+}
@@ -0,0 +1,70 @@
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, BorderType, Padding, Paragraph, Wrap};
+use crate::language::Messages;
+use crate::settings::{Setting, SettingsState};
+use crate::hit_areas::{HitAreas, Target};
+use crate::theme::Theme;
+
+pub struct SettingsWidget<'a> {
+    state: &'a SettingsState,
+    messages: &'a Messages,
+    help: String,
+    theme: &'a Theme,
+    hit_areas: Option<&'a mut HitAreas>
+}
+
+impl <'a> SettingsWidget<'a> {
+    pub fn new(state: &'a SettingsState, messages: &'a Messages, help: String, theme: &'a Theme) -> Self {
+        Self { state, messages, help, theme, hit_areas: None }
+    }
+
+    pub fn hit_areas(mut self, hit_areas: &'a mut HitAreas) -> Self {
+        self.hit_areas = Some(hit_areas);
+        self
+    }
+}
+
+impl <'a> Widget for SettingsWidget<'a> {
+    fn render(mut self, area: Rect, buf: &mut Buffer) where Self: Sized {
+
+        let block = Block::default()
+            .title(self.messages.settings_title)
+            .title_alignment(Alignment::Center)
+            .title_style(self.theme.title.add_modifier(Modifier::BOLD))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .padding(Padding::new(2, 2, 1, 1));
+
+        let entries = Setting::ALL.len();
+        let mut lines: Vec<Line> = Setting::ALL.iter().enumerate().map(|(i, setting)| {
+            let text = format!("{:<16} < {} >", setting.label(self.messages), setting.value(&self.state.config, self.messages));
+            if i == self.state.selected {
+                Line::styled(text, self.theme.title.add_modifier(Modifier::REVERSED))
+            } else {
+                Line::styled(text, self.theme.title)
+            }
+        }).collect();
+
+        lines.push(Line::default());
+        lines.push(Line::styled(self.help, self.theme.title));
+
+        if let Some(error) = &self.state.error {
+            lines.push(Line::default());
+            lines.push(Line::styled(error.clone(), self.theme.failure));
+        }
+
+        // Each entry is on its own line at the top of the block.
+        let inner = block.inner(area);
+        if let Some(hit_areas) = self.hit_areas.as_mut() {
+            for i in 0..entries {
+                hit_areas.add(Rect::new(inner.x, inner.y + i as u16, inner.width, 1).intersection(inner), Target::Entry(i));
+            }
+        }
+
+        Paragraph::new(lines)
+            .centered()
+            .wrap(Wrap { trim: true })
+            .block(block)
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Debug;
+use std::fs;
+use std::hash::Hash;
+use std::path::PathBuf;
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::style::{Color, Modifier};
+
+// Compares a rendered buffer against `snapshots/<name>.txt`. Run the tests
+// with UPDATE_SNAPSHOTS=1 to write new or changed snapshots, then review the
+// diff before committing it.
+pub fn assert_snapshot(name: &str, buffer: &Buffer) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("snapshots").join(format!("{name}.txt"));
+    let actual = render(buffer);
+
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("Missing snapshot {}, run with UPDATE_SNAPSHOTS=1 to create it", path.display()));
+
+    if expected != actual {
+        panic!("Snapshot {name} doesn't match, run with UPDATE_SNAPSHOTS=1 to update it\n\nexpected:\n{expected}\nactual:\n{actual}");
+    }
+}
+
+// The text of each row between bars, so trailing spaces survive editors, then
+// the same grid for the background colours, foreground colours and modifiers,
+// each value replaced by a letter from the legend below its grid.
+fn render(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut text = format!("{}x{}\n", area.width, area.height);
+    for y in area.top()..area.bottom() {
+        text.push('|');
+        for x in area.left()..area.right() {
+            text.push_str(buffer.get(x, y).symbol());
+        }
+        text.push_str("|\n");
+    }
+
+    text.push_str(&grid(buffer, Color::Reset, |cell| cell.bg));
+    text.push_str(&grid(buffer, Color::Reset, |cell| cell.fg));
+    text.push_str(&grid(buffer, Modifier::empty(), |cell| cell.modifier));
+    text
+}
+
+// Cells left at `blank` are spaces.
+fn grid<T: Copy + Eq + Hash + Debug>(buffer: &Buffer, blank: T, value: impl Fn(&Cell) -> T) -> String {
+    let area = buffer.area;
+    let mut values: Vec<T> = Vec::new();
+    let mut letters: HashMap<T, char> = HashMap::new();
+    let mut text = String::from("\n");
+
+    for y in area.top()..area.bottom() {
+        text.push('|');
+        for x in area.left()..area.right() {
+            let cell = value(buffer.get(x, y));
+            text.push(if cell == blank {
+                ' '
+            } else {
+                *letters.entry(cell).or_insert_with(|| {
+                    values.push(cell);
+                    (b'a' + (values.len() as u8 - 1) % 26) as char
+                })
+            });
+        }
+        text.push_str("|\n");
+    }
+
+    for value in values {
+        text.push_str(&format!("{}: {value:?}\n", letters[&value]));
+    }
+    text
+}
@@ -0,0 +1,125 @@
+use std::collections::{HashMap, HashSet};
+use crate::guess::score;
+use crate::letters::LetterState;
+
+// Guesses considered for a suggestion, the candidates covering the most common letters.
+const POOL: usize = 100;
+
+// Narrows down the answer from the colours of guesses, scored the way the game scores them.
+#[derive(Clone, Debug)]
+pub struct Solver {
+    candidates: Vec<String>
+}
+
+impl Solver {
+    pub fn new(words: &[String]) -> Self {
+        Self { candidates: words.to_vec() }
+    }
+
+    pub fn candidates(&self) -> &[String] {
+        &self.candidates
+    }
+
+    // Keeps the words that would have coloured the guess the same way.
+    pub fn apply(&mut self, guess: &str, feedback: &[LetterState]) {
+        self.candidates.retain(|candidate| score(guess, candidate) == feedback);
+    }
+
+    // The guess leaving the fewest candidates on average.
+    pub fn suggest(&self) -> Option<&str> {
+        if self.candidates.len() <= 2 {
+            return self.candidates.first().map(String::as_str);
+        }
+
+        let mut frequencies: HashMap<char, usize> = HashMap::new();
+        for candidate in &self.candidates {
+            for letter in candidate.chars().collect::<HashSet<_>>() {
+                *frequencies.entry(letter).or_default() += 1;
+            }
+        }
+
+        let mut pool: Vec<&String> = self.candidates.iter().collect();
+        pool.sort_by_cached_key(|word| {
+            let coverage: usize = word.chars().collect::<HashSet<_>>().iter().map(|letter| frequencies[letter]).sum();
+            std::cmp::Reverse(coverage)
+        });
+        pool.truncate(POOL);
+
+        pool.into_iter()
+            .min_by_key(|guess| self.remaining(guess))
+            .map(String::as_str)
+    }
+
+    // Sum of squared group sizes, proportional to the expected number of candidates left.
+    fn remaining(&self, guess: &str) -> usize {
+        let mut groups: HashMap<u64, usize> = HashMap::new();
+        for candidate in &self.candidates {
+            *groups.entry(pattern(guess, candidate)).or_default() += 1;
+        }
+        groups.values().map(|size| size * size).sum()
+    }
+}
+
+// Colours of a guess packed into a number, so they hash cheaply.
+pub fn pattern(guess: &str, answer: &str) -> u64 {
+    score(guess, answer).iter().fold(0, |pattern, state| pattern * 3 + match state {
+        LetterState::RightPosition => 2,
+        LetterState::WrongPosition => 1,
+        LetterState::NotOccurring | LetterState::NotChecked => 0
+    })
+}
+
+// Colours written one letter per tile: `g` green, `y` yellow and `.` grey.
+pub fn parse_feedback(feedback: &str) -> Result<Vec<LetterState>, String> {
+    feedback.chars().map(|c| match c.to_ascii_lowercase() {
+        'g' => Ok(LetterState::RightPosition),
+        'y' => Ok(LetterState::WrongPosition),
+        '.' | '-' | 'x' => Ok(LetterState::NotOccurring),
+        other => Err(format!("Invalid colour '{}' in '{}', expected g, y or .", other, feedback))
+    }).collect()
+}
+
+// The other way round, e.g. ".ggyg".
+pub fn format_feedback(feedback: &[LetterState]) -> String {
+    feedback.iter().map(|state| match state {
+        LetterState::RightPosition => 'g',
+        LetterState::WrongPosition => 'y',
+        LetterState::NotOccurring | LetterState::NotChecked => '.'
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::letters::LetterState;
+    use crate::solver::{format_feedback, parse_feedback, pattern, Solver};
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn should_keep_candidates_matching_feedback() {
+        let mut solver = Solver::new(&words(&["crane", "trace", "cable", "brave"]));
+        solver.apply("trace", &parse_feedback(".ggyg").unwrap());
+
+        assert_eq!(solver.candidates(), &words(&["crane"]));
+        assert_eq!(solver.suggest(), Some("crane"));
+    }
+
+    #[test]
+    fn should_suggest_guess_splitting_candidates_best() {
+        let solver = Solver::new(&words(&["bill", "fill", "hill", "pill", "will", "bolt"]));
+        let suggestion = solver.suggest().unwrap();
+
+        assert!(["bill", "bolt"].contains(&suggestion), "{}", suggestion);
+        assert_eq!(Solver::new(&[]).suggest(), None);
+    }
+
+    #[test]
+    fn should_parse_feedback() {
+        assert_eq!(parse_feedback("gY.").unwrap(), vec![LetterState::RightPosition, LetterState::WrongPosition, LetterState::NotOccurring]);
+        assert!(parse_feedback("gq").is_err());
+        assert_eq!(format_feedback(&parse_feedback("gy.").unwrap()), "gy.");
+        assert_eq!(pattern("crane", "crane"), 242);
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use crate::difficulty::Difficulty;
+use crate::paths;
+
+const STATS_FILE: &str = "stats.txt";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameRecord {
+    pub difficulty: Difficulty,
+    pub won: bool,
+    pub guesses: usize,
+    // Missing from records written before it was kept, like the words below.
+    pub tries: Option<u8>,
+    pub answer: String,
+    // Guessed words, oldest first.
+    pub words: Vec<String>
+}
+
+impl GameRecord {
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split_whitespace();
+        let difficulty = fields.next()?.parse().ok()?;
+        let won = match fields.next()? {
+            "won" => true,
+            "lost" => false,
+            _ => return None
+        };
+        let field = fields.next()?;
+        let (guesses, tries) = match field.split_once('/') {
+            Some((guesses, tries)) => (guesses.parse().ok()?, Some(tries.parse().ok()?)),
+            None => (field.parse().ok()?, None)
+        };
+        let answer = fields.next()?.to_string();
+        let words = fields.map(str::to_string).collect();
+        Some(Self { difficulty, won, guesses, tries, answer, words })
+    }
+
+    fn format(&self) -> String {
+        let guesses = match self.tries {
+            Some(tries) => format!("{}/{}", self.guesses, tries),
+            None => self.guesses.to_string()
+        };
+        let mut line = format!("{} {} {} {}", self.difficulty, if self.won { "won" } else { "lost" }, guesses, self.answer);
+        for word in &self.words {
+            line.push(' ');
+            line.push_str(word);
+        }
+        line
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub played: usize,
+    pub won: usize
+}
+
+impl Summary {
+    pub fn win_rate(&self) -> u32 {
+        (self.won * 100).checked_div(self.played).unwrap_or(0) as u32
+    }
+}
+
+pub struct Stats {
+    path: Option<PathBuf>,
+    records: Vec<GameRecord>
+}
+
+impl Stats {
+
+    pub fn load() -> Self {
+        let path = paths::data_dir().map(|dir| dir.join(STATS_FILE));
+        let records = path.as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|contents| contents.lines().filter_map(GameRecord::parse).collect())
+            .unwrap_or_default();
+
+        Self { path, records }
+    }
+
+    // Nothing is read or written, e.g. for games played by bots.
+    pub fn in_memory() -> Self {
+        Self { path: None, records: Vec::new() }
+    }
+
+    pub fn record(&mut self, record: GameRecord) -> io::Result<()> {
+        let line = record.format();
+        self.records.push(record);
+
+        match &self.path {
+            Some(path) => {
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+                writeln!(file, "{}", line)
+            },
+            None => Ok(())
+        }
+    }
+
+    pub fn last(&self) -> Option<&GameRecord> {
+        self.records.last()
+    }
+
+    pub fn by_difficulty(&self) -> BTreeMap<Difficulty, Summary> {
+        let mut summaries = BTreeMap::new();
+        for record in &self.records {
+            let summary: &mut Summary = summaries.entry(record.difficulty).or_default();
+            summary.played += 1;
+            if record.won {
+                summary.won += 1;
+            }
+        }
+        summaries
+    }
+
+    // Number of games won with each number of guesses.
+    pub fn distribution(&self, difficulty: Difficulty) -> BTreeMap<usize, usize> {
+        let mut distribution = BTreeMap::new();
+        for record in self.records.iter().filter(|record| record.won && record.difficulty == difficulty) {
+            *distribution.entry(record.guesses).or_default() += 1;
+        }
+        distribution
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::difficulty::Difficulty;
+    use crate::stats::{GameRecord, Stats, Summary};
+
+    fn record(difficulty: Difficulty, won: bool) -> GameRecord {
+        GameRecord { difficulty, won, guesses: 4, tries: None, answer: "crane".to_string(), words: Vec::new() }
+    }
+
+    #[test]
+    fn should_summarize_win_rates_per_difficulty() {
+        let mut stats = Stats::in_memory();
+        stats.record(record(Difficulty::Easy, true)).unwrap();
+        stats.record(record(Difficulty::Hard, true)).unwrap();
+        stats.record(record(Difficulty::Hard, false)).unwrap();
+
+        let summaries = stats.by_difficulty();
+        assert_eq!(summaries.get(&Difficulty::Easy), Some(&Summary { played: 1, won: 1 }));
+        assert_eq!(summaries.get(&Difficulty::Hard).map(Summary::win_rate), Some(50));
+        assert_eq!(summaries.get(&Difficulty::Normal), None);
+    }
+
+    #[test]
+    fn should_count_guesses_of_won_games() {
+        let mut stats = Stats::in_memory();
+        stats.record(record(Difficulty::Normal, true)).unwrap();
+        stats.record(record(Difficulty::Normal, false)).unwrap();
+        stats.record(record(Difficulty::Easy, true)).unwrap();
+
+        assert_eq!(stats.distribution(Difficulty::Normal).into_iter().collect::<Vec<_>>(), vec![(4, 1)]);
+    }
+
+    #[test]
+    fn should_parse_formatted_records() {
+        let record = record(Difficulty::Normal, false);
+        assert_eq!(GameRecord::parse(&record.format()), Some(record));
+    }
+
+    #[test]
+    fn should_keep_guessed_words() {
+        let with_words = GameRecord {
+            guesses: 2,
+            tries: Some(6),
+            words: vec!["trace".to_string(), "crane".to_string()],
+            ..record(Difficulty::Easy, true)
+        };
+        assert_eq!(with_words.format(), "easy won 2/6 crane trace crane");
+        assert_eq!(GameRecord::parse(&with_words.format()), Some(with_words));
+    }
+}
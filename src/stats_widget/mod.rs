@@ -0,0 +1,68 @@
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, BorderType, Padding, Paragraph};
+use crate::difficulty::Difficulty;
+use crate::language::Messages;
+use crate::stats::Stats;
+use crate::theme::Theme;
+
+const BAR_WIDTH: usize = 30;
+
+pub struct StatsWidget<'a> {
+    stats: &'a Stats,
+    difficulty: Difficulty,
+    messages: &'a Messages,
+    help: String,
+    theme: &'a Theme
+}
+
+impl <'a> StatsWidget<'a> {
+    pub fn new(stats: &'a Stats, difficulty: Difficulty, messages: &'a Messages, help: String, theme: &'a Theme) -> Self {
+        Self { stats, difficulty, messages, help, theme }
+    }
+}
+
+impl <'a> Widget for StatsWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) where Self: Sized {
+
+        let block = Block::default()
+            .title(self.messages.statistics_title)
+            .title_alignment(Alignment::Center)
+            .title_style(self.theme.title.add_modifier(Modifier::BOLD))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .padding(Padding::new(2, 2, 1, 1));
+
+        let summaries = self.stats.by_difficulty();
+        let mut lines = Vec::new();
+
+        if summaries.is_empty() {
+            lines.push(Line::styled(self.messages.no_games, self.theme.title));
+        } else {
+            let header = format!("{:<12} {:>8} {:>8} {:>8}", self.messages.difficulty_label, self.messages.played, self.messages.won_games, self.messages.win_rate);
+            lines.push(Line::styled(header, self.theme.title.add_modifier(Modifier::BOLD)));
+            for (difficulty, summary) in &summaries {
+                let row = format!("{:<12} {:>8} {:>8} {:>8}", self.messages.difficulty(*difficulty), summary.played, summary.won, summary.win_rate());
+                lines.push(Line::styled(row, self.theme.title));
+            }
+
+            let distribution = self.stats.distribution(self.difficulty);
+            if !distribution.is_empty() {
+                let most = distribution.values().copied().max().unwrap_or(1);
+                lines.push(Line::default());
+                lines.push(Line::styled(Messages::fill(self.messages.guess_distribution, &[("difficulty", &self.messages.difficulty(self.difficulty))]), self.theme.title));
+                for (guesses, count) in distribution {
+                    let bar = "█".repeat((count * BAR_WIDTH).div_ceil(most));
+                    lines.push(Line::styled(format!("{:>2} {:<width$} {:>4}", guesses, bar, count, width = BAR_WIDTH), self.theme.won));
+                }
+            }
+        }
+
+        lines.push(Line::default());
+        lines.push(Line::styled(self.help, self.theme.title));
+
+        Paragraph::new(lines)
+            .centered()
+            .block(block)
+            .render(area, buf);
+    }
+}
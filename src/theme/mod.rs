@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use ratatui::prelude::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+use crate::letters::LetterState;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Theme {
+    pub name: String,
+    pub right_position: Style,
+    pub wrong_position: Style,
+    pub not_checked: Style,
+    pub not_occurring: Style,
+    pub title: Style,
+    pub won: Style,
+    pub lost: Style,
+    pub failure: Style,
+    pub loading: Style
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColorsConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fg: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bg: Option<String>
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right_position: Option<ColorsConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wrong_position: Option<ColorsConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not_checked: Option<ColorsConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not_occurring: Option<ColorsConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<ColorsConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub won: Option<ColorsConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lost: Option<ColorsConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure: Option<ColorsConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loading: Option<ColorsConfig>
+}
+
+fn tile(bg: Color, fg: Color) -> Style {
+    Style::default().bg(bg).fg(fg)
+}
+
+fn title(fg: Color) -> Style {
+    Style::default().fg(fg).add_modifier(Modifier::BOLD)
+}
+
+impl Theme {
+
+    pub fn dark() -> Self {
+        Self {
+            name: "dark".to_string(),
+            right_position: tile(Color::Green, Color::White),
+            wrong_position: tile(Color::LightYellow, Color::Black),
+            not_checked: tile(Color::Gray, Color::Black),
+            not_occurring: tile(Color::DarkGray, Color::White),
+            title: Style::default().fg(Color::White),
+            won: title(Color::Green),
+            lost: title(Color::Red),
+            failure: title(Color::Red),
+            loading: title(Color::LightBlue)
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            name: "light".to_string(),
+            right_position: tile(Color::Rgb(106, 170, 100), Color::White),
+            wrong_position: tile(Color::Rgb(201, 180, 88), Color::White),
+            not_checked: tile(Color::Rgb(211, 214, 218), Color::Black),
+            not_occurring: tile(Color::Rgb(120, 124, 126), Color::White),
+            title: Style::default().fg(Color::Black),
+            won: title(Color::Rgb(106, 170, 100)),
+            lost: title(Color::Rgb(190, 30, 45)),
+            failure: title(Color::Rgb(190, 30, 45)),
+            loading: title(Color::Blue)
+        }
+    }
+
+    pub fn colour_blind() -> Self {
+        Self {
+            name: "colour-blind".to_string(),
+            right_position: tile(Color::Rgb(245, 121, 58), Color::Black),
+            wrong_position: tile(Color::Rgb(133, 192, 249), Color::Black),
+            not_checked: tile(Color::Gray, Color::Black),
+            not_occurring: tile(Color::DarkGray, Color::White),
+            title: Style::default().fg(Color::White),
+            won: title(Color::Rgb(245, 121, 58)),
+            lost: title(Color::Rgb(133, 192, 249)),
+            failure: title(Color::Rgb(245, 121, 58)),
+            loading: title(Color::Rgb(133, 192, 249))
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "high-contrast".to_string(),
+            right_position: tile(Color::LightGreen, Color::Black).add_modifier(Modifier::BOLD),
+            wrong_position: tile(Color::LightYellow, Color::Black).add_modifier(Modifier::BOLD),
+            not_checked: tile(Color::White, Color::Black).add_modifier(Modifier::BOLD),
+            not_occurring: tile(Color::Black, Color::White).add_modifier(Modifier::BOLD),
+            title: title(Color::White),
+            won: title(Color::LightGreen),
+            lost: title(Color::LightRed),
+            failure: title(Color::LightRed),
+            loading: title(Color::LightCyan)
+        }
+    }
+
+    pub fn built_in() -> Vec<Self> {
+        vec![Theme::dark(), Theme::light(), Theme::colour_blind(), Theme::high_contrast()]
+    }
+
+    // Built-in themes followed by the user's ones, which are based on `base` (dark by default).
+    pub fn all(user_themes: &BTreeMap<String, ThemeConfig>) -> Result<Vec<Self>, String> {
+        let mut themes = Theme::built_in();
+
+        for (name, config) in user_themes {
+            let base_name = config.base.as_deref().unwrap_or("dark");
+            let base = themes.iter()
+                .find(|theme| theme.name == base_name)
+                .cloned()
+                .ok_or(format!("Theme '{}' is based on unknown theme '{}'", name, base_name))?;
+
+            let theme = Theme {
+                name: name.clone(),
+                right_position: apply(base.right_position, &config.right_position)?,
+                wrong_position: apply(base.wrong_position, &config.wrong_position)?,
+                not_checked: apply(base.not_checked, &config.not_checked)?,
+                not_occurring: apply(base.not_occurring, &config.not_occurring)?,
+                title: apply(base.title, &config.title)?,
+                won: apply(base.won, &config.won)?,
+                lost: apply(base.lost, &config.lost)?,
+                failure: apply(base.failure, &config.failure)?,
+                loading: apply(base.loading, &config.loading)?
+            };
+
+            themes.retain(|existing| existing.name != theme.name);
+            themes.push(theme);
+        }
+
+        Ok(themes)
+    }
+
+    pub fn tile(&self, state: &LetterState) -> Style {
+        match state {
+            LetterState::RightPosition => self.right_position,
+            LetterState::WrongPosition => self.wrong_position,
+            LetterState::NotChecked => self.not_checked,
+            LetterState::NotOccurring => self.not_occurring
+        }
+    }
+}
+
+fn apply(style: Style, colors: &Option<ColorsConfig>) -> Result<Style, String> {
+    let colors = match colors {
+        Some(colors) => colors,
+        None => return Ok(style)
+    };
+
+    let parse = |value: &String| Color::from_str(value).map_err(|_| format!("Invalid colour '{}'", value));
+
+    let mut style = style;
+    if let Some(fg) = &colors.fg {
+        style = style.fg(parse(fg)?);
+    }
+    if let Some(bg) = &colors.bg {
+        style = style.bg(parse(bg)?);
+    }
+    Ok(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use ratatui::prelude::Color;
+    use crate::theme::{ColorsConfig, Theme, ThemeConfig};
+
+    #[test]
+    fn should_build_user_themes_on_top_of_base_theme() {
+        let config = ThemeConfig {
+            base: Some("light".to_string()),
+            right_position: Some(ColorsConfig { fg: None, bg: Some("#ff8800".to_string()) }),
+            ..ThemeConfig::default()
+        };
+        let themes = Theme::all(&BTreeMap::from([("team".to_string(), config)])).unwrap();
+        let team = themes.iter().find(|theme| theme.name == "team").unwrap();
+
+        assert_eq!(team.right_position.bg, Some(Color::Rgb(255, 136, 0)));
+        assert_eq!(team.wrong_position, Theme::light().wrong_position);
+    }
+
+    #[test]
+    fn should_reject_invalid_colours() {
+        let config = ThemeConfig {
+            won: Some(ColorsConfig { fg: Some("greenish".to_string()), bg: None }),
+            ..ThemeConfig::default()
+        };
+        assert!(Theme::all(&BTreeMap::from([("team".to_string(), config)])).is_err());
+    }
+}
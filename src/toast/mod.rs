@@ -0,0 +1,33 @@
+// About three seconds at one tick per 50 ms event poll timeout.
+const TOAST_TICKS: u16 = 60;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Toast {
+    pub message: String,
+    ticks_left: u16
+}
+
+impl Toast {
+
+    pub fn new(message: String) -> Self {
+        Self { message, ticks_left: TOAST_TICKS }
+    }
+
+    // Returns whether the toast should be hidden.
+    pub fn tick(&mut self) -> bool {
+        self.ticks_left = self.ticks_left.saturating_sub(1);
+        self.ticks_left == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::toast::Toast;
+
+    #[test]
+    fn should_expire_after_a_few_seconds() {
+        let mut toast = Toast::new("Not in word list".to_string());
+        assert!(!(0..59).any(|_| toast.tick()));
+        assert!(toast.tick());
+    }
+}
@@ -0,0 +1,384 @@
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, BorderType, Clear, Padding, Paragraph, Wrap};
+use crate::board_layout::BoardLayout;
+use crate::game_manager::{GameManager, GameManagerState};
+use crate::game_state::{GameState, GameStatus};
+use crate::guess_widget::GuessWidget;
+use crate::hit_areas::HitAreas;
+use crate::language::Messages;
+use crate::letters::LetterState;
+use crate::letters_widget::LettersWidget;
+use crate::menu_widget::MenuWidget;
+use crate::screen::{GameOption, Screen};
+use crate::settings_widget::SettingsWidget;
+use crate::stats_widget::StatsWidget;
+use crate::theme::Theme;
+
+// Rows for the text summary shown in accessible mode.
+const SUMMARY_HEIGHT: u16 = 2;
+
+pub fn ui(manager: &mut GameManager, frame: &mut Frame) {
+    let mut hit_areas = HitAreas::default();
+    draw(manager, frame, &mut hit_areas);
+    manager.set_hit_areas(hit_areas);
+}
+
+fn draw(manager: &mut GameManager, frame: &mut Frame, hit_areas: &mut HitAreas) {
+    let language = manager.language().clone();
+    let keyboard = manager.keyboard().to_vec();
+    let theme = manager.theme().clone();
+    let keys = manager.key_bindings().clone();
+    let messages = &language.messages;
+    let summary = manager.summary();
+    let record = Messages::fill(messages.record, &[
+        ("difficulty", &messages.difficulty(manager.difficulty())),
+        ("won", &summary.won),
+        ("played", &summary.played),
+        ("rate", &summary.win_rate())
+    ]);
+
+    let back = messages.with_keys(messages.back, &keys);
+    let difficulty = manager.difficulty();
+
+    if let Some(screen) = manager.screen() {
+        match screen {
+            Screen::MainMenu(menu) => {
+                let entries = menu.items.iter().map(|item| item.label(messages).to_string()).collect();
+                let help = messages.with_keys(messages.menu_help, &keys);
+                frame.render_widget(MenuWidget::new(messages.menu_title, entries, menu.selected, help, &theme).hit_areas(hit_areas), frame.size())
+            },
+            Screen::NewGame(new_game) => {
+                let entries = GameOption::ALL.iter()
+                    .map(|option| format!("{:<16} < {} >", option.label(messages), new_game.value(*option, messages)))
+                    .collect();
+                let help = messages.with_keys(messages.new_game_help, &keys);
+                frame.render_widget(MenuWidget::new(messages.new_game_title, entries, new_game.selected, help, &theme).hit_areas(hit_areas), frame.size())
+            },
+            Screen::Settings(settings) => {
+                let help = messages.with_keys(messages.settings_help, &keys);
+                frame.render_widget(SettingsWidget::new(settings, messages, help, &theme).hit_areas(hit_areas), frame.size())
+            },
+            Screen::Statistics =>
+                frame.render_widget(StatsWidget::new(manager.stats(), difficulty, messages, back, &theme), frame.size()),
+            Screen::Help => {
+                let text = format!("{}\n\n{}", messages.with_keys(messages.rules, &keys), back);
+                frame.render_widget(text_screen(messages.help_title, text, &theme), frame.size())
+            },
+            Screen::About => {
+                let text = format!("{}\n\n{}", Messages::fill(messages.about, &[("version", &env!("CARGO_PKG_VERSION"))]), back);
+                frame.render_widget(text_screen(messages.about_title, text, &theme), frame.size())
+            },
+            Screen::InvalidConfig(errors) => {
+                let mut lines = vec![Line::from(messages.invalid_config), Line::default()];
+                lines.extend(errors.iter().map(|error| Line::from(error.clone())));
+                lines.push(Line::default());
+                lines.push(Line::from(messages.with_keys(messages.failure_hint, &keys)));
+
+                frame.render_widget(
+                    Paragraph::new(lines)
+                        .centered()
+                        .style(theme.failure),
+                    frame.size()
+                )
+            }
+        }
+        return;
+    }
+
+    let animation = manager.animation().cloned();
+    let toast = manager.toast().map(|toast| toast.message.clone());
+    let pressed = manager.pressed();
+    let accessible = manager.accessible();
+    let summary_height = if accessible { SUMMARY_HEIGHT } else { 0 };
+
+    match manager.state {
+        GameManagerState::Playing(ref mut state) => {
+            let status = state.status.clone();
+            let target_word = state.target_word.clone();
+            let letters_widget = LettersWidget::new(state.letters.clone(), &keyboard, &theme)
+                .pressed(pressed)
+                .enter_ready(state.can_commit())
+                .marked(accessible);
+            let word_len = state.word_len();
+            let max_tries = state.max_tries as usize;
+
+            let (title, title_style) = match status {
+                GameStatus::Won =>
+                    (Messages::fill(&messages.with_keys(messages.won, &keys), &[("record", &record)]), theme.won),
+                GameStatus::Lost =>
+                    (Messages::fill(&messages.with_keys(messages.lost, &keys), &[("word", &target_word), ("record", &record)]), theme.lost),
+                _ =>
+                    (messages.with_keys(messages.help, &keys), theme.title)
+            };
+
+            let key_len = letters_widget.columns();
+            let key_rows = letters_widget.rows();
+
+            let board_area = Rect { height: frame.size().height.saturating_sub(summary_height), ..frame.size() };
+            let board_layout = match BoardLayout::fit(board_area, word_len, max_tries, key_rows, key_len) {
+                Some(board_layout) => board_layout,
+                None => {
+                    let (min_width, min_height) = BoardLayout::min_size(word_len, max_tries, key_rows, key_len);
+                    let min_height = min_height + summary_height;
+                    let text = Messages::fill(messages.too_small, &[
+                        ("width", &frame.size().width),
+                        ("height", &frame.size().height),
+                        ("min_width", &min_width),
+                        ("min_height", &min_height)
+                    ]);
+                    frame.render_widget(Paragraph::new(text).centered().wrap(Wrap { trim: true }).style(theme.failure), frame.size());
+                    return;
+                }
+            };
+
+            let bottom_block = Block::default()
+                .title(title)
+                .title_alignment(Alignment::Center)
+                .title_style(title_style)
+                .borders(Borders::TOP)
+                .padding(Padding::new(0, 0, 0, 1))
+                .border_type(BorderType::Double);
+
+            let layout = Layout::default()
+                .direction(Direction::Vertical)
+                .flex(Flex::Center)
+                .constraints(vec![
+                    Constraint::Length(1),
+                    Constraint::Length(board_layout.board_height()),
+                    Constraint::Length(1),
+                    Constraint::Length(summary_height),
+                    Constraint::Length(board_layout.keyboard_height() + 2),
+                ])
+                .split(frame.size());
+
+            if accessible {
+                frame.render_widget(
+                    Paragraph::new(describe(state, messages)).centered().wrap(Wrap { trim: true }).style(theme.title),
+                    layout[3]
+                );
+            }
+
+            GuessWidget::new(state, &theme, board_layout.board, animation.as_ref())
+                .marked(accessible)
+                .render(layout[1], frame.buffer_mut());
+
+            letters_widget
+                .tiles(board_layout.keys)
+                .hit_areas(hit_areas)
+                .render(bottom_block.inner(layout[4]), frame.buffer_mut());
+
+            frame.render_widget(
+                bottom_block,
+                layout[4]
+            );
+
+            if let Some(message) = toast {
+                render_toast(frame, message, layout[0], &theme);
+            }
+        },
+        GameManagerState::Failure(ref message) =>
+            frame.render_widget(
+                Paragraph::new(Messages::fill(&messages.with_keys(messages.failure, &keys), &[("message", message)]))
+                    .centered()
+                    .style(theme.failure),
+                frame.size()
+            ),
+        GameManagerState::Loading => {
+            frame.render_widget(
+                Paragraph::new(messages.loading)
+                    .centered()
+                    .style(theme.loading),
+                frame.size()
+            )
+        }
+    }
+}
+
+// The last guess and what's known about the letters, for screen readers.
+fn describe(state: &GameState, messages: &Messages) -> String {
+    let state_name = |state: &LetterState| match state {
+        LetterState::RightPosition => messages.correct,
+        LetterState::WrongPosition => messages.elsewhere,
+        LetterState::NotOccurring | LetterState::NotChecked => messages.absent
+    };
+    let list = |items: Vec<String>| if items.is_empty() { messages.none.to_string() } else { items.join(" ") };
+
+    let last_guess = match state.guess_count() {
+        0 => messages.no_guesses.to_string(),
+        count => {
+            let guess = state.get(count - 1);
+            let word: String = guess.letters().iter().map(|letter| letter.value).collect();
+            let letters: Vec<String> = guess.letters().iter()
+                .map(|letter| format!("{} {}", letter.value.to_uppercase(), state_name(&letter.state)))
+                .collect();
+            Messages::fill(messages.last_guess, &[("word", &word.to_uppercase()), ("letters", &letters.join(", "))])
+        }
+    };
+
+    let mut correct = Vec::new();
+    for i in 0..state.guess_count() {
+        for (position, letter) in state.get(i).letters().iter().enumerate() {
+            if letter.state == LetterState::RightPosition && !correct.contains(&(position, letter.value)) {
+                correct.push((position, letter.value));
+            }
+        }
+    }
+    correct.sort();
+
+    let with_state = |wanted: LetterState| state.letters.alphabet().iter()
+        .filter(|letter| state.letters.state(**letter) == wanted)
+        .map(|letter| letter.to_uppercase().to_string())
+        .collect::<Vec<_>>();
+
+    let known = Messages::fill(messages.known_letters, &[
+        ("correct", &list(correct.iter().map(|(position, letter)| format!("{}{}", letter.to_uppercase(), position + 1)).collect())),
+        ("elsewhere", &list(with_state(LetterState::WrongPosition))),
+        ("absent", &list(with_state(LetterState::NotOccurring)))
+    ]);
+
+    format!("{} {}", last_guess, known)
+}
+
+// Shown in the row left blank above the board.
+fn render_toast(frame: &mut Frame, message: String, area: Rect, theme: &Theme) {
+    let width = (message.chars().count() as u16 + 4).min(area.width);
+    let toast_area = Rect::new(area.x + (area.width - width) / 2, area.y, width, 1.min(area.height));
+
+    frame.render_widget(Clear, toast_area);
+    frame.render_widget(
+        Paragraph::new(message)
+            .centered()
+            .style(theme.title.add_modifier(Modifier::REVERSED | Modifier::BOLD)),
+        toast_area
+    );
+}
+
+fn text_screen<'a>(title: &'a str, text: String, theme: &Theme) -> Paragraph<'a> {
+    let block = Block::default()
+        .title(title)
+        .title_alignment(Alignment::Center)
+        .title_style(theme.title.add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .padding(Padding::new(2, 2, 1, 1));
+
+    Paragraph::new(text)
+        .centered()
+        .wrap(Wrap { trim: true })
+        .style(theme.title)
+        .block(block)
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use crate::action::Action;
+    use crate::config::Config;
+    use crate::game_manager::{GameManager, GameManagerState};
+    use crate::game_state::GameState;
+    use crate::history::History;
+    use crate::snapshot::assert_snapshot;
+    use crate::stats::Stats;
+    use crate::ui::{describe, ui};
+
+    fn manager(config: Config) -> GameManager {
+        let mut manager = GameManager::new(History::in_memory(), Stats::in_memory());
+        manager.configure(&Config { animations: Some(false), ..config });
+        manager
+    }
+
+    // A game with a known answer, played through the same actions as the keyboard.
+    fn game(target: &str, tries: u8, guesses: &[&str]) -> GameManager {
+        configured_game(Config::default(), target, tries, guesses)
+    }
+
+    fn configured_game(config: Config, target: &str, tries: u8, guesses: &[&str]) -> GameManager {
+        let mut manager = manager(config);
+        let state = GameState::new(target, tries, &manager.language().alphabet);
+        manager.state = GameManagerState::Playing(Box::new(state));
+        for guess in guesses {
+            guess.chars().for_each(|letter| { manager.perform(Action::Type(letter)); });
+            manager.perform(Action::Submit);
+        }
+        manager
+    }
+
+    fn assert_screen(name: &str, manager: &mut GameManager, width: u16, height: u16) {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| ui(manager, frame)).unwrap();
+        assert_snapshot(name, terminal.backend().buffer());
+    }
+
+    #[test]
+    fn should_render_loading_and_failure() {
+        let mut manager = manager(Config::default());
+        assert_screen("ui_loading", &mut manager, 40, 5);
+
+        manager.state = GameManagerState::Failure("no connection".to_string());
+        assert_screen("ui_failure", &mut manager, 40, 5);
+    }
+
+    #[test]
+    fn should_render_game_in_progress() {
+        let mut manager = game("crane", 6, &["trace"]);
+        manager.perform(Action::Type('c'));
+        assert_screen("ui_playing", &mut manager, 80, 40);
+    }
+
+    #[test]
+    fn should_render_won_and_lost_games() {
+        let mut manager = game("crane", 6, &["trace", "crane"]);
+        assert_screen("ui_won", &mut manager, 80, 40);
+
+        let mut manager = game("word", 2, &["wore", "cord"]);
+        assert_screen("ui_lost", &mut manager, 80, 40);
+    }
+
+    #[test]
+    fn should_render_other_word_lengths() {
+        let mut manager = game("letters", 7, &["settler"]);
+        assert_screen("ui_seven_letters", &mut manager, 80, 40);
+    }
+
+    #[test]
+    fn should_shrink_tiles_to_fit_small_terminals() {
+        let mut manager = game("crane", 6, &["trace"]);
+        assert_screen("ui_compact", &mut manager, 40, 16);
+        assert_screen("ui_too_small", &mut manager, 20, 6);
+    }
+
+    #[test]
+    fn should_render_menu_over_the_game() {
+        let mut manager = game("crane", 6, &["trace"]);
+        manager.open_menu();
+        assert_screen("ui_menu", &mut manager, 60, 20);
+    }
+
+    #[test]
+    fn should_mark_tiles_and_describe_guesses_in_accessible_mode() {
+        let config = Config { accessible: Some(true), ..Config::default() };
+        let mut manager = configured_game(config, "crane", 6, &["trace", "cable"]);
+        assert_screen("ui_accessible", &mut manager, 80, 40);
+    }
+
+    #[test]
+    fn should_describe_last_guess_and_known_letters() {
+        let manager = game("crane", 6, &["trace", "cable"]);
+        let GameManagerState::Playing(state) = &manager.state else {
+            panic!("Expected a game in progress");
+        };
+
+        assert_eq!(
+            describe(state, &manager.language().messages),
+            "Last guess CABLE: C correct, A elsewhere, B absent, L absent, E correct. Correct: C1 R2 A3 E5. Elsewhere: none. Absent: B L T."
+        );
+
+        let manager = game("crane", 6, &[]);
+        let GameManagerState::Playing(state) = &manager.state else {
+            panic!("Expected a game in progress");
+        };
+        assert_eq!(describe(state, &manager.language().messages), "No guesses yet. Correct: none. Elsewhere: none. Absent: none.");
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+use minreq::Error;
+use crate::difficulty::Difficulty;
+use crate::random::Random;
+use crate::words::WordPack;
+
+const API_ATTEMPTS: usize = 5;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PickError {
+    FetchFailed,
+    NoWords { word_len: u8, difficulty: Option<Difficulty>, pack: String }
+}
+
+pub enum WordSource {
+    Api,
+    Pack(WordPack)
+}
+
+impl WordSource {
+
+    fn fetch_word(word_len: u8) -> Result<String, Error> {
+        let response = minreq::get(format!("https://random-word-api.herokuapp.com/word?length={}", word_len)).send()?;
+        let json = response.json::<Vec<String>>()?;
+        json.first().map(|word| word.to_string()).ok_or(Error::Other("Didn't receive word from the API"))
+    }
+
+    // Picks a word outside `excluded`. When every candidate was played recently, falls back
+    // to the one played the longest ago (`games_since` returns `None` for unplayed words).
+    // API words come without frequency data, so the difficulty only narrows down packs,
+    // and without one every word of the pack can come up.
+    pub fn pick(
+        &self,
+        word_len: u8,
+        difficulty: Option<Difficulty>,
+        random: &mut Random,
+        excluded: &HashSet<&str>,
+        games_since: impl Fn(&str) -> Option<usize>
+    ) -> Result<String, PickError> {
+        match self {
+            WordSource::Api => {
+                let mut word = None;
+                for _ in 0..API_ATTEMPTS {
+                    let fetched = WordSource::fetch_word(word_len).map_err(|_| PickError::FetchFailed)?;
+                    let repeated = excluded.contains(fetched.as_str());
+                    word = Some(fetched);
+                    if !repeated {
+                        break;
+                    }
+                }
+                word.ok_or(PickError::FetchFailed)
+            },
+            WordSource::Pack(pack) => {
+                let words = pack.with_length(word_len as usize);
+                let candidates = match difficulty {
+                    Some(difficulty) => difficulty.candidates(words.words()),
+                    None => words.words().to_vec()
+                };
+                let fresh: Vec<String> = candidates.iter()
+                    .filter(|word| !excluded.contains(word.as_str()))
+                    .cloned()
+                    .collect();
+
+                match random.choose(&fresh) {
+                    Some(word) => Ok(word.clone()),
+                    None => candidates.iter()
+                        .max_by_key(|word| games_since(word).unwrap_or(usize::MAX))
+                        .cloned()
+                        .ok_or(PickError::NoWords { word_len, difficulty, pack: pack.name().to_string() })
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use crate::random::Random;
+    use crate::word_source::WordSource;
+    use crate::words::WordPack;
+
+    fn source() -> WordSource {
+        WordSource::Pack(WordPack::parse("test", "crane\nslate\ntrace\ntree"))
+    }
+
+    #[test]
+    fn should_not_pick_excluded_words() {
+        let excluded = HashSet::from(["crane", "slate"]);
+        let mut random = Random::with_seed(7);
+        for _ in 0..10 {
+            assert_eq!(source().pick(5, None, &mut random, &excluded, |_| None), Ok("trace".to_string()));
+        }
+    }
+
+    #[test]
+    fn should_fall_back_to_least_recently_played_word_when_pool_is_exhausted() {
+        let excluded = HashSet::from(["crane", "slate", "trace"]);
+        let games_since = |word: &str| match word {
+            "crane" => Some(0),
+            "slate" => Some(5),
+            _ => Some(2)
+        };
+        let picked = source().pick(5, None, &mut Random::with_seed(7), &excluded, games_since);
+        assert_eq!(picked, Ok("slate".to_string()));
+    }
+}
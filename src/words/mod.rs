@@ -0,0 +1,198 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+use unicode_segmentation::UnicodeSegmentation;
+use crate::paths;
+
+const EMBEDDED: [(&str, &str); 2] = [
+    ("en", include_str!("packs/en.txt")),
+    ("pl", include_str!("packs/pl.txt")),
+];
+
+const PACK_EXTENSION: &str = "txt";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WordPack {
+    name: String,
+    words: Vec<String>
+}
+
+impl WordPack {
+
+    pub fn new(name: &str, words: Vec<String>) -> Self {
+        let mut seen = HashSet::new();
+        let words = words.into_iter()
+            .filter_map(|word| normalize(&word))
+            .filter(|word| seen.insert(word.clone()))
+            .collect();
+
+        Self { name: name.to_string(), words }
+    }
+
+    pub fn parse(name: &str, contents: &str) -> Self {
+        let words = contents.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect();
+
+        WordPack::new(name, words)
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        let name = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+        Ok(WordPack::parse(&name, &contents))
+    }
+
+    pub fn embedded(name: &str) -> Option<Self> {
+        EMBEDDED.iter()
+            .find(|(pack_name, _)| *pack_name == name)
+            .map(|(pack_name, contents)| WordPack::parse(pack_name, contents))
+    }
+
+    pub fn all_embedded() -> Vec<Self> {
+        EMBEDDED.iter().map(|(name, contents)| WordPack::parse(name, contents)).collect()
+    }
+
+    pub fn user_packs() -> io::Result<Vec<Self>> {
+        let dir = match paths::packs_dir() {
+            Some(dir) if dir.is_dir() => dir,
+            _ => return Ok(Vec::new())
+        };
+
+        let mut packs = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == PACK_EXTENSION) {
+                packs.push(WordPack::load(&path)?);
+            }
+        }
+        packs.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(packs)
+    }
+
+    // Resolves a pack reference: an existing file, then an embedded pack, then a user pack.
+    pub fn resolve(reference: &str) -> Result<Self, String> {
+        let path = Path::new(reference);
+        if path.is_file() {
+            return WordPack::load(path).map_err(|e| format!("Failed to read '{}': {}", reference, e));
+        }
+
+        if let Some(pack) = WordPack::embedded(reference) {
+            return Ok(pack);
+        }
+
+        if let Some(path) = paths::packs_dir().map(|dir| dir.join(format!("{}.{}", reference, PACK_EXTENSION))) {
+            if path.is_file() {
+                return WordPack::load(&path).map_err(|e| format!("Failed to read '{}': {}", path.display(), e));
+            }
+        }
+
+        Err(format!("Unknown word pack '{}'", reference))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        normalize(word).is_some_and(|word| self.words.contains(&word))
+    }
+
+    pub fn count_by_length(&self) -> BTreeMap<usize, usize> {
+        let mut counts = BTreeMap::new();
+        for word in &self.words {
+            *counts.entry(word.chars().count()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    pub fn merge(name: &str, packs: &[WordPack]) -> Self {
+        let words = packs.iter().flat_map(|pack| pack.words.iter().cloned()).collect();
+        WordPack::new(name, words)
+    }
+
+    pub fn with_length(&self, length: usize) -> Self {
+        self.filtered(|word| word.chars().count() == length)
+    }
+
+    // Keeps only words spelled entirely with the given letters.
+    pub fn with_letters(&self, letters: &str) -> Self {
+        let allowed: HashSet<char> = letters.chars().flat_map(char::to_lowercase).collect();
+        self.filtered(|word| word.chars().all(|c| allowed.contains(&c)))
+    }
+
+    fn filtered(&self, predicate: impl Fn(&str) -> bool) -> Self {
+        Self {
+            name: self.name.clone(),
+            words: self.words.iter().filter(|word| predicate(word)).cloned().collect()
+        }
+    }
+
+    // The format of the packs under `src/words/packs`: one lowercase word per line, no comments.
+    pub fn to_embedded(&self) -> String {
+        self.words.iter().map(|word| format!("{}\n", word)).collect()
+    }
+}
+
+// Every letter has to be a single char, so words using combining marks are skipped.
+fn normalize(word: &str) -> Option<String> {
+    let word = word.trim().to_lowercase();
+    if !word.is_empty() && word.chars().all(char::is_alphabetic) && word.graphemes(true).count() == word.chars().count() {
+        Some(word)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::words::WordPack;
+
+    #[test]
+    fn should_skip_comments_blank_lines_and_duplicates() {
+        let pack = WordPack::parse("test", "# team pack\ncrane\n\nSlate\ncrane\nno-go\nzo\u{0308}e\n");
+        assert_eq!(pack.words(), &["crane".to_string(), "slate".to_string()]);
+    }
+
+    #[test]
+    fn should_count_words_by_length() {
+        let pack = WordPack::parse("test", "crane\nslate\ntree\nłódka");
+        let counts = pack.count_by_length();
+        assert_eq!(counts.get(&4), Some(&1));
+        assert_eq!(counts.get(&5), Some(&3));
+    }
+
+    #[test]
+    fn should_merge_and_dedupe_packs() {
+        let first = WordPack::parse("first", "crane\nslate");
+        let second = WordPack::parse("second", "slate\ntrace");
+        let merged = WordPack::merge("merged", &[first, second]);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged.to_embedded(), "crane\nslate\ntrace\n");
+    }
+
+    #[test]
+    fn should_filter_by_length_and_letters() {
+        let pack = WordPack::parse("test", "crane\nslate\ntree\nrace");
+        assert_eq!(pack.with_length(4).words(), &["tree".to_string(), "race".to_string()]);
+        assert_eq!(pack.with_letters("ACERN").words(), &["crane".to_string(), "race".to_string()]);
+    }
+
+    #[test]
+    fn should_check_words_case_insensitively() {
+        let pack = WordPack::embedded("en").unwrap();
+        assert!(pack.contains("CRANE"));
+        assert!(!pack.contains("xxxxx"));
+    }
+}
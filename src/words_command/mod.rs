@@ -0,0 +1,154 @@
+use std::fs;
+use crate::words::WordPack;
+
+const USAGE: &str = "Usage: wordle words <command>
+
+Commands:
+  list                                  List embedded and user word packs
+  count <pack>                          Count words by length
+  check <word> <pack>...                Check whether a word is in the given packs
+  merge <pack>... [--output <file>]     Merge packs and drop duplicates
+  filter <pack> [--length <n>] [--letters <letters>] [--output <file>]
+                                        Keep words of a given length or letter set
+  export <pack> [--output <file>]       Write a pack in the embedded format
+
+A pack is a file path, an embedded pack name or the name of a pack in the user packs directory.";
+
+struct Options {
+    packs: Vec<String>,
+    length: Option<usize>,
+    letters: Option<String>,
+    output: Option<String>
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = Options { packs: Vec::new(), length: None, letters: None, output: None };
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--length" => {
+                    let value = args.next().ok_or("Missing value for --length")?;
+                    options.length = Some(value.parse().map_err(|_| format!("Invalid length '{}'", value))?);
+                },
+                "--letters" => options.letters = Some(args.next().ok_or("Missing value for --letters")?.clone()),
+                "--output" | "-o" => options.output = Some(args.next().ok_or("Missing value for --output")?.clone()),
+                flag if flag.starts_with("--") => return Err(format!("Unknown option '{}'", flag)),
+                pack => options.packs.push(pack.to_string())
+            }
+        }
+
+        Ok(options)
+    }
+
+    fn single_pack(&self) -> Result<WordPack, String> {
+        match self.packs.as_slice() {
+            [pack] => WordPack::resolve(pack),
+            [] => Err("Missing word pack".to_string()),
+            _ => Err("Expected a single word pack".to_string())
+        }
+    }
+
+    fn all_packs(&self) -> Result<Vec<WordPack>, String> {
+        if self.packs.is_empty() {
+            return Err("Missing word pack".to_string());
+        }
+        self.packs.iter().map(|pack| WordPack::resolve(pack)).collect()
+    }
+
+    fn write(&self, contents: &str) -> Result<(), String> {
+        match &self.output {
+            Some(path) => fs::write(path, contents).map_err(|e| format!("Failed to write '{}': {}", path, e)),
+            None => {
+                print!("{}", contents);
+                Ok(())
+            }
+        }
+    }
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (command, rest) = match args.split_first() {
+        Some((command, rest)) => (command.as_str(), rest),
+        None => {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+    };
+
+    match command {
+        "list" => list(),
+        "count" => count(&Options::parse(rest)?),
+        "check" => match rest.split_first() {
+            Some((word, packs)) => check(word, &Options::parse(packs)?),
+            None => Err("Missing word to check".to_string())
+        },
+        "merge" => merge(&Options::parse(rest)?),
+        "filter" => filter(&Options::parse(rest)?),
+        "export" => export(&Options::parse(rest)?),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Ok(())
+        },
+        other => Err(format!("Unknown command 'words {}'\n\n{}", other, USAGE))
+    }
+}
+
+fn list() -> Result<(), String> {
+    for pack in WordPack::all_embedded() {
+        println!("{:<16} {:>7} words  (embedded)", pack.name(), pack.len());
+    }
+
+    let user_packs = WordPack::user_packs().map_err(|e| format!("Failed to read user packs: {}", e))?;
+    for pack in user_packs {
+        println!("{:<16} {:>7} words  (user)", pack.name(), pack.len());
+    }
+
+    Ok(())
+}
+
+fn count(options: &Options) -> Result<(), String> {
+    let pack = options.single_pack()?;
+
+    for (length, count) in pack.count_by_length() {
+        println!("{:>3} letters: {}", length, count);
+    }
+    println!("      total: {}", pack.len());
+
+    Ok(())
+}
+
+fn check(word: &str, options: &Options) -> Result<(), String> {
+    let packs = options.all_packs()?;
+    let found: Vec<&str> = packs.iter().filter(|pack| pack.contains(word)).map(|pack| pack.name()).collect();
+
+    if found.is_empty() {
+        Err(format!("'{}' is not in any of the given packs", word))
+    } else {
+        println!("'{}' found in: {}", word, found.join(", "));
+        Ok(())
+    }
+}
+
+fn merge(options: &Options) -> Result<(), String> {
+    let merged = WordPack::merge("merged", &options.all_packs()?);
+    options.write(&merged.to_embedded())
+}
+
+fn filter(options: &Options) -> Result<(), String> {
+    let mut pack = options.single_pack()?;
+
+    if let Some(length) = options.length {
+        pack = pack.with_length(length);
+    }
+    if let Some(letters) = &options.letters {
+        pack = pack.with_letters(letters);
+    }
+
+    options.write(&pack.to_embedded())
+}
+
+fn export(options: &Options) -> Result<(), String> {
+    options.write(&options.single_pack()?.to_embedded())
+}